@@ -0,0 +1,111 @@
+//! Internal SHAKE256/SHA3-256 authenticated encryption.
+//!
+//! The keystream is SHAKE256 over the key and nonce; the tag is a keyed SHA3-256 over
+//! the nonce, associated data and ciphertext. SHA-3 is not length-extendable, so the
+//! prefix-keyed hash is a sound MAC.
+
+use alloc::vec::Vec;
+use crate::{ct_eq, QuantumError};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Shake256};
+
+/// Length of the authentication tag appended to every sealed message.
+pub(crate) const TAG_LEN: usize = 32;
+
+/// Absorbs `bytes` preceded by its length, so adjacent fields cannot be confused.
+pub(crate) fn absorb_framed(hasher: &mut impl Update, bytes: &[u8]) {
+    absorb_len(hasher, bytes.len());
+    hasher.update(bytes);
+}
+
+/// Absorbs the length prefix `absorb_framed` writes, for a field hashed in pieces.
+pub(crate) fn absorb_len(hasher: &mut impl Update, len: usize) {
+    hasher.update(&(len as u64).to_be_bytes());
+}
+
+/// Returns the keystream reader for `key` and `nonce`.
+pub(crate) fn keystream(key: &[u8], nonce: &[u8]) -> impl XofReader {
+    let mut xof = Shake256::default();
+    xof.update(b"ruption-aead-stream-v1");
+    absorb_framed(&mut xof, key);
+    absorb_framed(&mut xof, nonce);
+    xof.finalize_xof()
+}
+
+/// XORs the next `data.len()` keystream bytes into `data`.
+pub(crate) fn apply_keystream(reader: &mut impl XofReader, data: &mut [u8]) {
+    let mut block = [0u8; 64];
+    for chunk in data.chunks_mut(block.len()) {
+        let pad = &mut block[..chunk.len()];
+        reader.read(pad);
+        for (d, k) in chunk.iter_mut().zip(pad.iter()) {
+            *d ^= k;
+        }
+    }
+}
+
+/// Starts the tag computation; feed the ciphertext, then call `finish_tag`.
+pub(crate) fn start_tag(key: &[u8], nonce: &[u8], aad: &[u8]) -> Sha3_256 {
+    let mut mac = Sha3_256::new();
+    Update::update(&mut mac, b"ruption-aead-mac-v1");
+    absorb_framed(&mut mac, key);
+    absorb_framed(&mut mac, nonce);
+    absorb_framed(&mut mac, aad);
+    mac
+}
+
+/// Completes a tag over `ciphertext_len` bytes of absorbed ciphertext.
+pub(crate) fn finish_tag(mut mac: Sha3_256, ciphertext_len: u64) -> [u8; TAG_LEN] {
+    Update::update(&mut mac, &ciphertext_len.to_be_bytes());
+    mac.finalize().into()
+}
+
+/// Encrypts `plaintext` and appends the tag.
+pub(crate) fn seal(key: &[u8], nonce: &[u8], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut out = plaintext.to_vec();
+    apply_keystream(&mut keystream(key, nonce), &mut out);
+    let mut mac = start_tag(key, nonce, aad);
+    Update::update(&mut mac, &out);
+    let tag = finish_tag(mac, out.len() as u64);
+    out.extend_from_slice(&tag);
+    out
+}
+
+/// Verifies the tag in constant time and decrypts.
+///
+/// Returns `QuantumError::AuthenticationFailed` without releasing any plaintext on mismatch.
+pub(crate) fn open(key: &[u8], nonce: &[u8], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, QuantumError> {
+    if sealed.len() < TAG_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+    let mut mac = start_tag(key, nonce, aad);
+    Update::update(&mut mac, ciphertext);
+    let expected = finish_tag(mac, ciphertext.len() as u64);
+    if !ct_eq(&expected, tag) {
+        return Err(QuantumError::AuthenticationFailed);
+    }
+    let mut out = ciphertext.to_vec();
+    apply_keystream(&mut keystream(key, nonce), &mut out);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_open_round_trip_and_tamper() {
+        let key = [7u8; 32];
+        let sealed = seal(&key, b"nonce", b"aad", b"attack at dawn");
+        assert_eq!(sealed.len(), 14 + TAG_LEN);
+        assert_eq!(open(&key, b"nonce", b"aad", &sealed).unwrap(), b"attack at dawn");
+
+        let mut tampered = sealed.clone();
+        tampered[0] ^= 1;
+        assert_eq!(open(&key, b"nonce", b"aad", &tampered), Err(QuantumError::AuthenticationFailed));
+        assert!(open(&key, b"nonce", b"other", &sealed).is_err());
+        assert!(open(&key, b"other", b"aad", &sealed).is_err());
+        assert_eq!(open(&key, b"nonce", b"aad", &sealed[..5]), Err(QuantumError::InvalidLength));
+    }
+}
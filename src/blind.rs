@@ -0,0 +1,93 @@
+//! Toy-strength public-key blinding for private key-directory lookups.
+//!
+//! Blinding XORs every public key byte with a SHAKE256 keystream derived from the blinding
+//! factor, so it is exactly reversible with the same factor. It is not a cryptographic
+//! blinding scheme: two keys blinded with the same factor leak their XOR, and nothing ties a
+//! blinded key to a valid public key.
+
+use alloc::vec::Vec;
+use crate::aead::apply_keystream;
+use crate::PublicKey;
+use sha3::digest::{ExtendableOutput, Update};
+use sha3::Shake256;
+
+/// A public key hidden under a blinding factor; see `PublicKey::blind`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlindedKey {
+    lattice_matrix: Vec<Vec<u8>>, // Blinded lattice matrix rows
+    code_generator: Vec<u8>,      // Blinded code generator
+}
+
+impl PublicKey {
+    /// Hides this key under `blinding`, e.g. a random 32-byte value.
+    ///
+    /// The same key and factor always give the same `BlindedKey`, so a directory can match
+    /// blinded keys by equality without seeing the keys. Toy strength only; see the module
+    /// documentation for what it does not protect against.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::keypair;
+    /// let (pk, _sk) = keypair();
+    /// let blinded = pk.blind(b"random factor");
+    /// assert_eq!(blinded, pk.blind(b"random factor"));
+    /// assert_ne!(blinded, pk.blind(b"another factor"));
+    /// ```
+    pub fn blind(&self, blinding: &[u8]) -> BlindedKey {
+        let (lattice_matrix, code_generator) = mask(&self._lattice_matrix, &self._code_generator, blinding);
+        BlindedKey {
+            lattice_matrix,
+            code_generator,
+        }
+    }
+}
+
+impl BlindedKey {
+    /// Recovers the original `PublicKey` using the factor it was blinded with.
+    ///
+    /// A wrong factor yields an unrelated, useless key rather than an error.
+    pub fn unblind(&self, blinding: &[u8]) -> PublicKey {
+        let (lattice_matrix, code_generator) = mask(&self.lattice_matrix, &self.code_generator, blinding);
+        PublicKey {
+            _lattice_matrix: lattice_matrix,
+            _code_generator: code_generator,
+        }
+    }
+}
+
+/// XORs the key components with the keystream for `blinding`; applying it twice is a no-op.
+fn mask(lattice_matrix: &[Vec<u8>], code_generator: &[u8], blinding: &[u8]) -> (Vec<Vec<u8>>, Vec<u8>) {
+    let mut xof = Shake256::default();
+    xof.update(b"ruption-blind-v1");
+    xof.update(&(blinding.len() as u64).to_be_bytes());
+    xof.update(blinding);
+    let mut reader = xof.finalize_xof();
+
+    let mut apply = |component: &[u8]| {
+        let mut out = component.to_vec();
+        apply_keystream(&mut reader, &mut out);
+        out
+    };
+    let matrix = lattice_matrix.iter().map(|row| apply(row)).collect();
+    let generator = apply(code_generator);
+    (matrix, generator)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::keypair;
+
+    #[test]
+    fn test_blind_unblind_round_trip() {
+        let (pk, _sk) = keypair();
+        let blinded = pk.blind(b"factor-1");
+        let recovered = blinded.unblind(b"factor-1");
+        assert_eq!(recovered._lattice_matrix, pk._lattice_matrix);
+        assert_eq!(recovered._code_generator, pk._code_generator);
+
+        let other = pk.blind(b"factor-2");
+        assert_ne!(blinded, other);
+        assert_ne!(blinded.code_generator, pk._code_generator);
+        assert_ne!(blinded.unblind(b"factor-2")._code_generator, pk._code_generator);
+    }
+}
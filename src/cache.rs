@@ -0,0 +1,144 @@
+//! Decapsulation with a bounded cache of recent results.
+//!
+//! Useful when the same ciphertext may arrive more than once, e.g. on retransmission. Hits are
+//! keyed by a hash of the ciphertext's canonical encoding, never by its raw components.
+
+use alloc::vec::Vec;
+use crate::{decapsulate, Ciphertext, SecretKey, SharedSecret};
+use sha3::digest::Update;
+use sha3::{Digest, Sha3_256};
+use alloc::collections::VecDeque;
+use zeroize::Zeroizing;
+
+/// Decapsulates with a bounded LRU cache of recent results.
+///
+/// Entries are keyed by a SHA3-256 fingerprint of the whole ciphertext. Cached secrets are
+/// wiped when evicted and when the decapsulator is dropped.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, CachingDecapsulator};
+/// let (pk, sk) = keypair();
+/// let (ct, ss) = encapsulate(&pk);
+/// let mut decapsulator = CachingDecapsulator::new(sk, 16);
+/// assert_eq!(decapsulator.decapsulate(&ct).as_bytes(), ss.as_bytes());
+/// assert!(decapsulator.contains(&ct));
+/// ```
+pub struct CachingDecapsulator {
+    sk: SecretKey,                                     // Recipient secret key
+    capacity: usize,                                   // Maximum number of cached entries
+    entries: VecDeque<([u8; 32], Zeroizing<Vec<u8>>)>, // Least recently used first
+}
+
+impl CachingDecapsulator {
+    /// Creates a decapsulator that remembers up to `capacity` results.
+    ///
+    /// A capacity of zero disables caching.
+    pub fn new(sk: SecretKey, capacity: usize) -> Self {
+        CachingDecapsulator {
+            sk,
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Decapsulates `ct`, reusing the cached secret if this ciphertext was seen recently.
+    pub fn decapsulate(&mut self, ct: &Ciphertext) -> SharedSecret {
+        let fingerprint = fingerprint(ct);
+        if let Some(pos) = self.position(&fingerprint) {
+            let entry = self.entries.remove(pos).expect("position is in bounds");
+            let secret = SharedSecret::new(entry.1.to_vec());
+            self.entries.push_back(entry);
+            return secret;
+        }
+
+        let secret = decapsulate(ct, &self.sk);
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
+            self.entries.push_back((fingerprint, Zeroizing::new(secret.0.clone())));
+        }
+        secret
+    }
+
+    /// Returns `true` if the result for `ct` is currently cached.
+    pub fn contains(&self, ct: &Ciphertext) -> bool {
+        self.position(&fingerprint(ct)).is_some()
+    }
+
+    /// Returns the number of cached entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn position(&self, fingerprint: &[u8; 32]) -> Option<usize> {
+        self.entries.iter().position(|(fp, _)| fp == fingerprint)
+    }
+}
+
+/// Hashes the canonical encoding of the ciphertext into a cache key.
+fn fingerprint(ct: &Ciphertext) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, b"ruption-ciphertext-fingerprint");
+    Update::update(&mut hasher, &ct.to_bytes());
+    hasher.finalize().into()
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{encapsulate, keypair};
+
+    #[test]
+    fn test_second_decapsulation_hits_cache() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let mut decapsulator = CachingDecapsulator::new(sk, 2);
+
+        assert!(!decapsulator.contains(&ct));
+        let first = decapsulator.decapsulate(&ct);
+        assert!(decapsulator.contains(&ct));
+        let second = decapsulator.decapsulate(&ct);
+        assert_eq!(first.as_bytes(), ss.as_bytes());
+        assert_eq!(second.as_bytes(), ss.as_bytes());
+        assert_eq!(decapsulator.len(), 1);
+    }
+
+    #[test]
+    fn test_cache_is_bounded_lru() {
+        let (pk, sk) = keypair();
+        let cts: Vec<Ciphertext> = (0..3).map(|_| encapsulate(&pk).0).collect();
+        let mut decapsulator = CachingDecapsulator::new(sk, 2);
+
+        decapsulator.decapsulate(&cts[0]);
+        decapsulator.decapsulate(&cts[1]);
+        decapsulator.decapsulate(&cts[0]); // refresh cts[0]
+        decapsulator.decapsulate(&cts[2]); // evicts cts[1]
+        assert_eq!(decapsulator.len(), 2);
+        assert!(decapsulator.contains(&cts[0]));
+        assert!(!decapsulator.contains(&cts[1]));
+        assert!(decapsulator.contains(&cts[2]));
+
+        let (_pk, sk) = keypair();
+        let mut uncached = CachingDecapsulator::new(sk, 0);
+        uncached.decapsulate(&cts[0]);
+        assert!(uncached.is_empty());
+    }
+
+    #[test]
+    fn test_fingerprint_frames_components() {
+        let (pk, _sk) = keypair();
+        let (ct, _ss) = encapsulate(&pk);
+        // Moving the component boundary keeps the concatenated bytes but changes the encoding.
+        let mut shifted = ct.clone();
+        let last = shifted.lattice_cipher.pop().unwrap();
+        shifted.code_cipher.insert(0, last);
+        assert_ne!(fingerprint(&shifted), fingerprint(&ct));
+    }
+}
@@ -0,0 +1,39 @@
+//! Length-prefixed binary framing shared by the crate's encodings.
+//!
+//! Lengths and counts are big-endian `u32`, so every value has exactly one encoding.
+
+use alloc::vec::Vec;
+use crate::QuantumError;
+
+/// Appends `value` as a big-endian `u32`.
+pub(crate) fn put_u32(out: &mut Vec<u8>, value: usize) {
+    out.extend_from_slice(&(value as u32).to_be_bytes());
+}
+
+/// Appends `bytes` preceded by its `u32` length.
+pub(crate) fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    put_u32(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+/// Splits a big-endian `u32` off the front of `rest`.
+pub(crate) fn take_u32(rest: &mut &[u8]) -> Result<u32, QuantumError> {
+    let head = take_bytes(rest, 4)?;
+    Ok(u32::from_be_bytes([head[0], head[1], head[2], head[3]]))
+}
+
+/// Splits exactly `len` bytes off the front of `rest`.
+pub(crate) fn take_bytes<'a>(rest: &mut &'a [u8], len: usize) -> Result<&'a [u8], QuantumError> {
+    if rest.len() < len {
+        return Err(QuantumError::Deserialize);
+    }
+    let (head, tail) = rest.split_at(len);
+    *rest = tail;
+    Ok(head)
+}
+
+/// Splits a `u32`-length-prefixed byte string off the front of `rest`.
+pub(crate) fn take_prefixed<'a>(rest: &mut &'a [u8]) -> Result<&'a [u8], QuantumError> {
+    let len = take_u32(rest)? as usize;
+    take_bytes(rest, len)
+}
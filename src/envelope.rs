@@ -0,0 +1,278 @@
+//! Multi-recipient envelopes: one body, one KEM slot per recipient.
+//!
+//! A random content key encrypts the body once. Each slot holds the recipient's
+//! `PublicKey::fingerprint`, the KEM ciphertext's `Ciphertext::to_bytes` encoding, and the
+//! content key wrapped under a key derived from that encapsulation. The wrap authenticates
+//! the fingerprint, so a slot moved to another recipient fails to unwrap instead of yielding
+//! a key.
+//!
+//! `seal_group_key` is the same wrapping without a body, for protocols that keep one group
+//! key and encrypt their own messages under it.
+
+use alloc::vec::Vec;
+use crate::aead;
+#[cfg(feature = "std")]
+use crate::codec::{put_bytes, put_u32};
+use crate::codec::{take_bytes, take_prefixed, take_u32};
+use crate::decapsulate;
+#[cfg(feature = "std")]
+use crate::{encapsulate_batch, fill_random};
+use crate::{Ciphertext, PublicKey, QuantumError, SecretKey, SharedSecret};
+use zeroize::Zeroizing;
+
+/// Length of the `PublicKey::fingerprint` that labels each slot.
+const FINGERPRINT_LEN: usize = 16;
+const CONTENT_KEY_LEN: usize = 32;
+const SLOT_AAD: &[u8] = b"ruption-envelope-slot-v1";
+const WRAP_NONCE: &[u8] = b"ruption-envelope-wrap";
+const BODY_NONCE: &[u8] = b"ruption-envelope-body";
+const GROUP_WRAP_NONCE: &[u8] = b"ruption-group-key-wrap";
+
+/// Encrypts `plaintext` once so that each of `recipients` can decrypt it.
+///
+/// # Arguments
+/// * `recipients` - The public keys to address; each gets its own slot.
+/// * `plaintext` - The message to encrypt.
+///
+/// # Returns
+/// The encoded envelope: the recipient slots, then the encrypted body, which also
+/// authenticates every slot.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encrypt_to_many, decrypt_from_many};
+/// let (alice_pk, alice_sk) = keypair();
+/// let (bob_pk, bob_sk) = keypair();
+/// let envelope = encrypt_to_many(&[&alice_pk, &bob_pk], b"hello both");
+/// assert_eq!(decrypt_from_many(&envelope, &alice_sk, &alice_pk).unwrap(), b"hello both");
+/// assert_eq!(decrypt_from_many(&envelope, &bob_sk, &bob_pk).unwrap(), b"hello both");
+/// ```
+#[cfg(feature = "std")]
+pub fn encrypt_to_many(recipients: &[&PublicKey], plaintext: &[u8]) -> Vec<u8> {
+    let mut content_key = Zeroizing::new([0u8; CONTENT_KEY_LEN]);
+    fill_random(&mut content_key[..]);
+
+    let mut header = Vec::new();
+    put_u32(&mut header, recipients.len());
+    for (pk, (ct, ss)) in recipients.iter().zip(encapsulate_batch(recipients)) {
+        put_slot(&mut header, &pk.fingerprint(), &ct, &ss, &content_key[..]);
+    }
+
+    let body = aead::seal(&content_key[..], BODY_NONCE, &header, plaintext);
+    header.extend(body);
+    header
+}
+
+/// Decrypts an envelope from `encrypt_to_many` as the holder of `sk`.
+///
+/// # Arguments
+/// * `envelope` - The encoded envelope.
+/// * `sk` - My `SecretKey`.
+/// * `pk` - My `PublicKey`, used to find my slot.
+///
+/// # Returns
+/// The plaintext, `QuantumError::WrongRecipient` if no slot is addressed to `pk` or my slot's
+/// key was wrapped for someone else, `QuantumError::AuthenticationFailed` if the envelope was
+/// modified, or `QuantumError::Deserialize` if it is malformed (`QuantumError::InvalidLength`
+/// if my slot's ciphertext has an impossible size).
+pub fn decrypt_from_many(envelope: &[u8], sk: &SecretKey, pk: &PublicKey) -> Result<Vec<u8>, QuantumError> {
+    let mine = pk.fingerprint();
+    let mut rest = envelope;
+    let count = take_u32(&mut rest)?;
+    let mut my_slot = None;
+    for _ in 0..count {
+        let fingerprint = take_bytes(&mut rest, FINGERPRINT_LEN)?;
+        let ciphertext = take_prefixed(&mut rest)?;
+        let wrapped = take_bytes(&mut rest, CONTENT_KEY_LEN + aead::TAG_LEN)?;
+        if my_slot.is_none() && fingerprint == mine {
+            my_slot = Some((ciphertext, wrapped));
+        }
+    }
+    let header = &envelope[..envelope.len() - rest.len()];
+    let (ciphertext, wrapped) = my_slot.ok_or(QuantumError::WrongRecipient)?;
+
+    let ct = Ciphertext::from_bytes_strict(ciphertext)?;
+    let wrap_key = wrap_key(&decapsulate(&ct, sk));
+    let content_key = Zeroizing::new(
+        aead::open(&wrap_key[..], WRAP_NONCE, &slot_aad(&mine), wrapped).map_err(|_| QuantumError::WrongRecipient)?,
+    );
+    aead::open(&content_key, BODY_NONCE, header, rest)
+}
+
+/// One recipient's share of a group key from `seal_group_key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupKeyWrap {
+    /// The KEM ciphertext addressed to the recipient.
+    pub ciphertext: Ciphertext,
+    /// The group key sealed under a key derived from that encapsulation.
+    pub wrapped: Vec<u8>,
+}
+
+/// Generates a random group key and wraps it for each of `recipients`.
+///
+/// Each wrap is authenticated together with its ciphertext, so a wrap paired with another
+/// recipient's ciphertext fails to open.
+///
+/// # Arguments
+/// * `recipients` - The group members' public keys.
+///
+/// # Returns
+/// The 32-byte group key, and one `GroupKeyWrap` per recipient in order.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, seal_group_key, open_group_key};
+/// let (alice_pk, alice_sk) = keypair();
+/// let (bob_pk, bob_sk) = keypair();
+/// let (group_key, wraps) = seal_group_key(&[alice_pk, bob_pk]);
+/// assert_eq!(open_group_key(&wraps[0], &alice_sk).unwrap(), group_key);
+/// assert_eq!(open_group_key(&wraps[1], &bob_sk).unwrap(), group_key);
+/// ```
+#[cfg(feature = "std")]
+pub fn seal_group_key(recipients: &[PublicKey]) -> (Zeroizing<[u8; 32]>, Vec<GroupKeyWrap>) {
+    let mut group_key = Zeroizing::new([0u8; CONTENT_KEY_LEN]);
+    fill_random(&mut group_key[..]);
+    let wraps = encapsulate_batch(recipients)
+        .into_iter()
+        .map(|(ct, ss)| {
+            let wrapped = aead::seal(&group_wrap_key(&ss)[..], GROUP_WRAP_NONCE, &ct.to_bytes(), &group_key[..]);
+            GroupKeyWrap {
+                ciphertext: ct,
+                wrapped,
+            }
+        })
+        .collect();
+    (group_key, wraps)
+}
+
+/// Recovers the group key from one recipient's `GroupKeyWrap`.
+///
+/// # Returns
+/// The group key, or `QuantumError::AuthenticationFailed` if `sk` is not the recipient's key
+/// or the ciphertext or wrap was modified, or taken from different recipients' shares.
+pub fn open_group_key(wrap: &GroupKeyWrap, sk: &SecretKey) -> Result<Zeroizing<[u8; 32]>, QuantumError> {
+    let ct = &wrap.ciphertext;
+    let wrap_key = group_wrap_key(&decapsulate(ct, sk));
+    let opened = Zeroizing::new(aead::open(&wrap_key[..], GROUP_WRAP_NONCE, &ct.to_bytes(), &wrap.wrapped)?);
+    if opened.len() != CONTENT_KEY_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    let mut group_key = Zeroizing::new([0u8; CONTENT_KEY_LEN]);
+    group_key.copy_from_slice(&opened);
+    Ok(group_key)
+}
+
+/// Appends the slot labelled `fingerprint` that wraps `content_key` for the holder of `ss`.
+#[cfg(feature = "std")]
+fn put_slot(
+    header: &mut Vec<u8>,
+    fingerprint: &[u8; FINGERPRINT_LEN],
+    ct: &Ciphertext,
+    ss: &SharedSecret,
+    content_key: &[u8],
+) {
+    header.extend_from_slice(fingerprint);
+    put_bytes(header, &ct.to_bytes());
+    header.extend(aead::seal(&wrap_key(ss)[..], WRAP_NONCE, &slot_aad(fingerprint), content_key));
+}
+
+fn slot_aad(fingerprint: &[u8; FINGERPRINT_LEN]) -> Vec<u8> {
+    [SLOT_AAD, &fingerprint[..]].concat()
+}
+
+fn wrap_key(ss: &SharedSecret) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    ss.expand(b"ruption-envelope-wrap-key", &mut key[..]);
+    key
+}
+
+fn group_wrap_key(ss: &SharedSecret) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    ss.expand(b"ruption-group-wrap-key", &mut key[..]);
+    key
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{encapsulate, keypair};
+
+    /// Builds a one-slot envelope encapsulated to and labelled for `pk`, whose wrap authenticates
+    /// the fingerprint of `wrapped_for` instead.
+    fn relabelled_envelope(pk: &PublicKey, wrapped_for: &PublicKey) -> Vec<u8> {
+        let content_key = [9u8; CONTENT_KEY_LEN];
+        let (ct, ss) = encapsulate(pk);
+        let mut header = Vec::new();
+        put_u32(&mut header, 1);
+        put_slot(&mut header, &wrapped_for.fingerprint(), &ct, &ss, &content_key);
+        header[4..4 + FINGERPRINT_LEN].copy_from_slice(&pk.fingerprint());
+        let body = aead::seal(&content_key, BODY_NONCE, &header, b"relabelled");
+        header.extend(body);
+        header
+    }
+
+    #[test]
+    fn test_swapped_slots_are_rejected() {
+        let (alice_pk, alice_sk) = keypair();
+        let (bob_pk, bob_sk) = keypair();
+        let envelope = encrypt_to_many(&[&alice_pk, &bob_pk], b"broadcast");
+        let len = (envelope.len() - 4 - (b"broadcast".len() + aead::TAG_LEN)) / 2;
+
+        // Swap everything after the fingerprints, leaving each label in place.
+        let mut swapped = envelope.clone();
+        let (a, b) = (4 + FINGERPRINT_LEN, 4 + len + FINGERPRINT_LEN);
+        let payload = len - FINGERPRINT_LEN;
+        let alice_payload = envelope[a..a + payload].to_vec();
+        swapped.copy_within(b..b + payload, a);
+        swapped[b..b + payload].copy_from_slice(&alice_payload);
+
+        assert_eq!(decrypt_from_many(&swapped, &alice_sk, &alice_pk), Err(QuantumError::WrongRecipient));
+        assert_eq!(decrypt_from_many(&swapped, &bob_sk, &bob_pk), Err(QuantumError::WrongRecipient));
+        assert_eq!(decrypt_from_many(&envelope, &bob_sk, &bob_pk).unwrap(), b"broadcast");
+    }
+
+    #[test]
+    fn test_slot_wrapped_for_another_fingerprint_is_rejected() {
+        let (alice_pk, alice_sk) = keypair();
+        let (bob_pk, _bob_sk) = keypair();
+        let honest = relabelled_envelope(&alice_pk, &alice_pk);
+        assert_eq!(decrypt_from_many(&honest, &alice_sk, &alice_pk).unwrap(), b"relabelled");
+
+        // Encapsulated to Alice and labelled for her, but the wrap names Bob's fingerprint.
+        let substituted = relabelled_envelope(&alice_pk, &bob_pk);
+        assert_eq!(decrypt_from_many(&substituted, &alice_sk, &alice_pk), Err(QuantumError::WrongRecipient));
+    }
+
+    #[test]
+    fn test_envelope_rejects_outsiders_and_tampering() {
+        let (alice_pk, alice_sk) = keypair();
+        let (carol_pk, carol_sk) = keypair();
+        let envelope = encrypt_to_many(&[&alice_pk], b"just alice");
+        assert_eq!(decrypt_from_many(&envelope, &carol_sk, &carol_pk), Err(QuantumError::WrongRecipient));
+
+        let mut tampered = envelope.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(decrypt_from_many(&tampered, &alice_sk, &alice_pk), Err(QuantumError::AuthenticationFailed));
+        assert_eq!(decrypt_from_many(&envelope[..40], &alice_sk, &alice_pk), Err(QuantumError::Deserialize));
+        // The slot carries the canonical ciphertext encoding, magic included.
+        let mut bad_magic = envelope.clone();
+        bad_magic[4 + FINGERPRINT_LEN + 4] ^= 1;
+        assert_eq!(decrypt_from_many(&bad_magic, &alice_sk, &alice_pk), Err(QuantumError::Deserialize));
+        assert!(decrypt_from_many(&encrypt_to_many(&[], b"nobody"), &alice_sk, &alice_pk).is_err());
+    }
+
+    #[test]
+    fn test_group_key_wraps_are_bound_to_their_ciphertext() {
+        let (alice_pk, alice_sk) = keypair();
+        let (bob_pk, bob_sk) = keypair();
+        let (group_key, wraps) = seal_group_key(&[alice_pk, bob_pk]);
+        assert_eq!(open_group_key(&wraps[1], &bob_sk).unwrap(), group_key);
+
+        let swapped = GroupKeyWrap {
+            ciphertext: wraps[0].ciphertext.clone(),
+            wrapped: wraps[1].wrapped.clone(),
+        };
+        assert_eq!(open_group_key(&swapped, &alice_sk), Err(QuantumError::AuthenticationFailed));
+        assert_eq!(open_group_key(&wraps[0], &bob_sk), Err(QuantumError::AuthenticationFailed));
+    }
+}
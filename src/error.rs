@@ -0,0 +1,52 @@
+//! The crate's error type.
+
+use core::fmt;
+
+/// Errors returned by the fallible operations in this crate.
+///
+/// Kept deliberately coarse: callers learn what failed, never secret-dependent details.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QuantumError {
+    /// An authentication tag or confirmation value did not verify.
+    AuthenticationFailed,
+    /// Input had the wrong length for the expected object.
+    InvalidLength,
+    /// A parameter set is structurally invalid.
+    InvalidParams,
+    /// A message was replayed, reordered, or too far ahead of the expected sequence number.
+    ReplayDetected,
+    /// Encoded bytes could not be decoded into the expected object.
+    Deserialize,
+    /// The message was not encrypted to this key, or its recipient slot was substituted.
+    WrongRecipient,
+    /// The random number generator could not produce output.
+    EntropyUnavailable,
+    /// Decapsulation did not recover a valid shared secret.
+    DecapsulationFailed,
+    /// The entropy source failed a continuous health test.
+    HealthFailure,
+    /// Reading or writing a file failed.
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+impl fmt::Display for QuantumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuantumError::AuthenticationFailed => write!(f, "authentication failed"),
+            QuantumError::InvalidLength => write!(f, "input has an invalid length"),
+            QuantumError::InvalidParams => write!(f, "invalid parameter set"),
+            QuantumError::ReplayDetected => write!(f, "message replayed or out of order"),
+            QuantumError::Deserialize => write!(f, "malformed encoding"),
+            QuantumError::WrongRecipient => write!(f, "message is not addressed to this key"),
+            QuantumError::EntropyUnavailable => write!(f, "entropy source unavailable"),
+            QuantumError::DecapsulationFailed => write!(f, "decapsulation failed"),
+            QuantumError::HealthFailure => write!(f, "entropy source failed a continuous health test"),
+            #[cfg(feature = "std")]
+            QuantumError::Io(kind) => write!(f, "I/O error: {kind}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for QuantumError {}
@@ -0,0 +1,82 @@
+//! The process-wide random generator behind the crate's implicit-RNG entry points.
+//!
+//! It is seeded from the OS on first use and reseeded periodically; see `global_rng`.
+
+use crate::{QuantumError, SharedRandom, TrueRandom};
+use std::sync::OnceLock;
+
+/// Number of draws between reseeds of the global generator from the OS.
+const RESEED_INTERVAL: u64 = 256;
+
+/// A handle to the process-wide `TrueRandom`, returned by `global_rng`.
+///
+/// All handles share one `SharedRandom`. It is seeded from the OS once, on first use, and
+/// reseeded every few hundred draws, so the default API does not pay for a fresh OS seed on
+/// every call.
+#[derive(Clone, Copy)]
+pub struct GlobalRng {
+    shared: &'static SharedRandom, // The single shared instance
+}
+
+/// Returns a handle to the shared, lazily initialized generator.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::global_rng;
+/// let mut nonce = [0u8; 16];
+/// global_rng().fill(&mut nonce);
+/// assert_eq!(global_rng().generate(32).len(), 32);
+/// ```
+pub fn global_rng() -> GlobalRng {
+    static SHARED: OnceLock<SharedRandom> = OnceLock::new();
+    GlobalRng {
+        shared: SHARED.get_or_init(|| {
+            let mut trng = TrueRandom::new();
+            trng.reseed_interval = Some(RESEED_INTERVAL);
+            SharedRandom::from(trng)
+        }),
+    }
+}
+
+impl GlobalRng {
+    /// Generates `len` random bytes from the shared generator.
+    ///
+    /// # Panics
+    /// If the OS entropy source fails; see `try_generate`.
+    pub fn generate(&self, len: usize) -> Vec<u8> {
+        self.shared.generate(len)
+    }
+
+    /// Generates `len` random bytes, reporting entropy failures instead of panicking.
+    ///
+    /// # Returns
+    /// The random bytes, or the error from `TrueRandom::try_generate` or a failed reseed.
+    pub fn try_generate(&self, len: usize) -> Result<Vec<u8>, QuantumError> {
+        self.shared.try_generate(len)
+    }
+
+    /// Fills `dest` with random bytes from the shared generator.
+    pub fn fill(&self, dest: &mut [u8]) {
+        self.shared.fill(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_global_rng_is_shared_but_varying() {
+        let a = global_rng();
+        let b = global_rng();
+        assert!(std::ptr::eq(a.shared, b.shared));
+
+        let first = a.generate(32);
+        let second = b.generate(32);
+        assert_ne!(first, second);
+
+        let mut filled = [0u8; 48];
+        a.fill(&mut filled);
+        assert_ne!(filled, [0u8; 48]);
+    }
+}
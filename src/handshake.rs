@@ -0,0 +1,364 @@
+//! Three-message handshake: KEM encapsulation followed by mutual key confirmation.
+//!
+//! Both sides hash a transcript of the protocol label, the responder's public key digest and
+//! the encoded ciphertext, then derive confirmation and session keys from the KEM secret and
+//! that transcript. Each encoded message starts with a type byte, so one kind of message
+//! never parses as another.
+
+use alloc::vec::Vec;
+use crate::aead::absorb_framed;
+use crate::codec::{put_bytes, take_bytes, take_prefixed};
+#[cfg(feature = "std")]
+use crate::{encapsulate, public_digest, PublicKey};
+use crate::{ct_eq, decapsulate, Ciphertext, QuantumError, SecretKey, SharedSecret};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{Digest, Sha3_256, Shake256};
+use zeroize::{Zeroize, Zeroizing};
+
+const TRANSCRIPT_LABEL: &[u8] = b"ruption-handshake-v2";
+const INITIATOR_TYPE: u8 = 1;  // Leading byte of an encoded InitiatorMessage
+const RESPONDER_TYPE: u8 = 2;  // Leading byte of an encoded ResponderMessage
+const CONFIRM_TYPE: u8 = 3;    // Leading byte of an encoded ConfirmMessage
+
+/// First handshake message, sent by the initiator to the responder.
+///
+/// Carries the KEM ciphertext encapsulated to the responder's public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitiatorMessage {
+    ciphertext: Ciphertext,
+}
+
+impl InitiatorMessage {
+    /// Returns the KEM ciphertext addressed to the responder.
+    pub fn ciphertext(&self) -> &Ciphertext {
+        &self.ciphertext
+    }
+
+    /// Encodes the message: its type byte, then `Ciphertext::to_bytes` behind a `u32` length.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = alloc::vec![INITIATOR_TYPE];
+        put_bytes(&mut out, &self.ciphertext.to_bytes());
+        out
+    }
+
+    /// Decodes a message encoded by `to_bytes`.
+    ///
+    /// # Returns
+    /// The message, or `QuantumError::Deserialize` if the type byte is wrong, the input is
+    /// truncated or has trailing bytes, or the ciphertext is not canonically encoded.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QuantumError> {
+        let mut rest = bytes;
+        if take_bytes(&mut rest, 1)? != [INITIATOR_TYPE] {
+            return Err(QuantumError::Deserialize);
+        }
+        let ciphertext = Ciphertext::from_bytes_strict(take_prefixed(&mut rest)?)?;
+        if !rest.is_empty() {
+            return Err(QuantumError::Deserialize);
+        }
+        Ok(InitiatorMessage { ciphertext })
+    }
+}
+
+/// Second handshake message, sent by the responder back to the initiator.
+///
+/// Proves the responder recovered the same shared secret over the same transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponderMessage {
+    tag: [u8; 32],
+}
+
+impl ResponderMessage {
+    /// Returns the responder's confirmation tag.
+    pub fn tag(&self) -> &[u8; 32] {
+        &self.tag
+    }
+
+    /// Encodes the message: its type byte, then the 32-byte tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_tag(RESPONDER_TYPE, &self.tag)
+    }
+
+    /// Decodes a message encoded by `to_bytes`.
+    ///
+    /// # Returns
+    /// The message, or `QuantumError::Deserialize` if the type byte or length is wrong.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QuantumError> {
+        Ok(ResponderMessage {
+            tag: decode_tag(RESPONDER_TYPE, bytes)?,
+        })
+    }
+}
+
+/// Third handshake message, sent by the initiator to confirm the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfirmMessage {
+    tag: [u8; 32],
+}
+
+impl ConfirmMessage {
+    /// Returns the initiator's confirmation tag.
+    pub fn tag(&self) -> &[u8; 32] {
+        &self.tag
+    }
+
+    /// Encodes the message: its type byte, then the 32-byte tag.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode_tag(CONFIRM_TYPE, &self.tag)
+    }
+
+    /// Decodes a message encoded by `to_bytes`.
+    ///
+    /// # Returns
+    /// The message, or `QuantumError::Deserialize` if the type byte or length is wrong.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QuantumError> {
+        Ok(ConfirmMessage {
+            tag: decode_tag(CONFIRM_TYPE, bytes)?,
+        })
+    }
+}
+
+fn encode_tag(kind: u8, tag: &[u8; 32]) -> Vec<u8> {
+    let mut out = alloc::vec![kind];
+    out.extend_from_slice(tag);
+    out
+}
+
+fn decode_tag(kind: u8, bytes: &[u8]) -> Result<[u8; 32], QuantumError> {
+    let mut rest = bytes;
+    if take_bytes(&mut rest, 1)? != [kind] {
+        return Err(QuantumError::Deserialize);
+    }
+    let tag = take_bytes(&mut rest, 32)?;
+    if !rest.is_empty() {
+        return Err(QuantumError::Deserialize);
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(tag);
+    Ok(out)
+}
+
+/// Initiator side of the three-message handshake.
+///
+/// Flow: `Handshake::start` → [`InitiatorMessage`], `Handshake::respond` → [`ResponderMessage`],
+/// `Handshake::finish` → [`Session`] plus a [`ConfirmMessage`] for `Responder::confirm`.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, Handshake};
+/// let (pk, sk) = keypair();
+/// let (initiator, msg1) = Handshake::start(&pk);
+/// let (responder, msg2) = Handshake::respond(&msg1, &sk);
+/// let (alice, msg3) = initiator.finish(&msg2).unwrap();
+/// let bob = responder.confirm(&msg3).unwrap();
+/// assert_eq!(alice.shared_secret().as_bytes(), bob.shared_secret().as_bytes());
+/// ```
+pub struct Handshake {
+    keys: HandshakeKeys,
+}
+
+/// Responder side of the handshake, waiting for the initiator's confirmation.
+pub struct Responder {
+    keys: HandshakeKeys,
+}
+
+/// A confirmed handshake session.
+///
+/// Both sides hold the same session secret and transcript hash once confirmed.
+pub struct Session {
+    secret: SharedSecret,
+    transcript: [u8; 32],
+}
+
+impl Session {
+    /// Returns the session secret, ready for `derive_keys`.
+    pub fn shared_secret(&self) -> &SharedSecret {
+        &self.secret
+    }
+
+    /// Returns the hash of the handshake transcript the session is bound to.
+    pub fn transcript_hash(&self) -> &[u8; 32] {
+        &self.transcript
+    }
+}
+
+/// Keys derived from the KEM secret and the transcript; wiped on drop.
+struct HandshakeKeys {
+    transcript: [u8; 32],
+    responder_key: Zeroizing<[u8; 32]>,
+    initiator_key: Zeroizing<[u8; 32]>,
+    session_key: Zeroizing<[u8; 32]>,
+}
+
+/// Hashes the protocol label, the responder's public key digest and the encoded ciphertext.
+fn transcript_hash(responder_digest: &[u8; 32], ct: &Ciphertext) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, TRANSCRIPT_LABEL);
+    absorb_framed(&mut hasher, responder_digest);
+    absorb_framed(&mut hasher, &ct.to_bytes());
+    hasher.finalize().into()
+}
+
+impl HandshakeKeys {
+    fn derive(responder_digest: &[u8; 32], ct: &Ciphertext, mut shared: SharedSecret) -> Self {
+        let transcript = transcript_hash(responder_digest, ct);
+
+        let mut xof = Shake256::default();
+        xof.update(TRANSCRIPT_LABEL);
+        xof.update(&shared.0);
+        xof.update(&transcript);
+        let mut reader = xof.finalize_xof();
+        shared.0.zeroize();
+
+        let mut keys = HandshakeKeys {
+            transcript,
+            responder_key: Zeroizing::new([0u8; 32]),
+            initiator_key: Zeroizing::new([0u8; 32]),
+            session_key: Zeroizing::new([0u8; 32]),
+        };
+        reader.read(&mut keys.responder_key[..]);
+        reader.read(&mut keys.initiator_key[..]);
+        reader.read(&mut keys.session_key[..]);
+        keys
+    }
+
+    fn tag(&self, key: &[u8; 32], role: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        Update::update(&mut hasher, key);
+        Update::update(&mut hasher, role);
+        Update::update(&mut hasher, &self.transcript);
+        hasher.finalize().into()
+    }
+
+    fn responder_tag(&self) -> [u8; 32] {
+        self.tag(&self.responder_key, b"responder")
+    }
+
+    fn initiator_tag(&self) -> [u8; 32] {
+        self.tag(&self.initiator_key, b"initiator")
+    }
+
+    fn into_session(self) -> Session {
+        Session {
+            secret: SharedSecret::new(self.session_key.to_vec()),
+            transcript: self.transcript,
+        }
+    }
+}
+
+impl Handshake {
+    /// Starts a handshake towards the holder of `their_pk`.
+    ///
+    /// # Returns
+    /// The initiator state and the message to send to the responder.
+    #[cfg(feature = "std")]
+    pub fn start(their_pk: &PublicKey) -> (Handshake, InitiatorMessage) {
+        let (ciphertext, shared) = encapsulate(their_pk);
+        let keys = HandshakeKeys::derive(&public_digest(their_pk), &ciphertext, shared);
+        (Handshake { keys }, InitiatorMessage { ciphertext })
+    }
+
+    /// Answers an initiator message using the responder's secret key.
+    ///
+    /// # Returns
+    /// The responder state and the message carrying the responder's confirmation tag.
+    pub fn respond(msg: &InitiatorMessage, my_sk: &SecretKey) -> (Responder, ResponderMessage) {
+        let shared = decapsulate(&msg.ciphertext, my_sk);
+        let keys = HandshakeKeys::derive(&my_sk.public_digest, &msg.ciphertext, shared);
+        let tag = keys.responder_tag();
+        (Responder { keys }, ResponderMessage { tag })
+    }
+
+    /// Verifies the responder's tag and completes the initiator side.
+    ///
+    /// Returns `QuantumError::AuthenticationFailed` if any message was tampered with.
+    ///
+    /// # Returns
+    /// The confirmed session and the final message for the responder.
+    pub fn finish(self, resp: &ResponderMessage) -> Result<(Session, ConfirmMessage), QuantumError> {
+        if !ct_eq(&self.keys.responder_tag(), &resp.tag) {
+            return Err(QuantumError::AuthenticationFailed);
+        }
+        let tag = self.keys.initiator_tag();
+        Ok((self.keys.into_session(), ConfirmMessage { tag }))
+    }
+}
+
+impl Responder {
+    /// Verifies the initiator's confirmation and completes the responder side.
+    ///
+    /// Returns `QuantumError::AuthenticationFailed` if the confirmation does not match.
+    pub fn confirm(self, msg: &ConfirmMessage) -> Result<Session, QuantumError> {
+        if !ct_eq(&self.keys.initiator_tag(), &msg.tag) {
+            return Err(QuantumError::AuthenticationFailed);
+        }
+        Ok(self.keys.into_session())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::keypair;
+
+    #[test]
+    fn test_handshake_confirms_shared_session() {
+        let (pk, sk) = keypair();
+        let (initiator, msg1) = Handshake::start(&pk);
+        let (responder, msg2) = Handshake::respond(&msg1, &sk);
+        let (alice, msg3) = initiator.finish(&msg2).unwrap();
+        let bob = responder.confirm(&msg3).unwrap();
+
+        assert_eq!(alice.shared_secret().as_bytes(), bob.shared_secret().as_bytes());
+        assert_eq!(alice.transcript_hash(), bob.transcript_hash());
+    }
+
+    #[test]
+    fn test_handshake_tampered_message_aborts() {
+        let (pk, sk) = keypair();
+        let (initiator, mut msg1) = Handshake::start(&pk);
+        msg1.ciphertext.code_cipher[0] ^= 1;
+        let (_responder, msg2) = Handshake::respond(&msg1, &sk);
+        assert_eq!(initiator.finish(&msg2).err(), Some(QuantumError::AuthenticationFailed));
+
+        let (initiator, msg1) = Handshake::start(&pk);
+        let (responder, msg2) = Handshake::respond(&msg1, &sk);
+        let (_session, mut msg3) = initiator.finish(&msg2).unwrap();
+        msg3.tag[31] ^= 1;
+        assert_eq!(responder.confirm(&msg3).err(), Some(QuantumError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_messages_round_trip_through_bytes() {
+        let (pk, sk) = keypair();
+        let (initiator, msg1) = Handshake::start(&pk);
+        let msg1_bytes = msg1.to_bytes();
+        assert_eq!(InitiatorMessage::from_bytes(&msg1_bytes).unwrap(), msg1);
+        let (responder, msg2) = Handshake::respond(&InitiatorMessage::from_bytes(&msg1_bytes).unwrap(), &sk);
+        let msg2_bytes = msg2.to_bytes();
+        let (alice, msg3) = initiator.finish(&ResponderMessage::from_bytes(&msg2_bytes).unwrap()).unwrap();
+        let msg3_bytes = msg3.to_bytes();
+        let bob = responder.confirm(&ConfirmMessage::from_bytes(&msg3_bytes).unwrap()).unwrap();
+        assert_eq!(alice.transcript_hash(), bob.transcript_hash());
+
+        for bytes in [&msg1_bytes, &msg2_bytes, &msg3_bytes] {
+            assert!(InitiatorMessage::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+            assert_eq!(ResponderMessage::from_bytes(&bytes[..bytes.len() - 1]), Err(QuantumError::Deserialize));
+            assert_eq!(ConfirmMessage::from_bytes(&bytes[..bytes.len() - 1]), Err(QuantumError::Deserialize));
+        }
+        // A confirmation is not a valid responder message, even though both carry one tag.
+        assert_eq!(ResponderMessage::from_bytes(&msg3_bytes), Err(QuantumError::Deserialize));
+        assert_eq!(ConfirmMessage::from_bytes(&[&msg3_bytes[..], &[0]].concat()), Err(QuantumError::Deserialize));
+        assert_eq!(InitiatorMessage::from_bytes(&[&msg1_bytes[..], &[0]].concat()), Err(QuantumError::Deserialize));
+    }
+
+    #[test]
+    fn test_transcript_binds_responder_key() {
+        let (pk, _sk) = keypair();
+        let (other_pk, _other_sk) = keypair();
+        let (ct, _ss) = crate::encapsulate(&pk);
+        let transcript = transcript_hash(&public_digest(&pk), &ct);
+        assert_ne!(transcript_hash(&public_digest(&other_pk), &ct), transcript);
+        let (other_ct, _ss) = crate::encapsulate(&pk);
+        assert_ne!(transcript_hash(&public_digest(&pk), &other_ct), transcript);
+    }
+}
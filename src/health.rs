@@ -0,0 +1,128 @@
+//! SP 800-90B health tests for the raw jitter samples and the output of `TrueRandom`.
+//!
+//! For raw samples both tests assume a conservative 1 bit of min-entropy per sample and a
+//! false-positive rate of 2^-20 per test, which gives the cutoffs below (SP 800-90B, sections
+//! 4.4.1–4.4.2). Conditioned output should carry a full 8 bits per byte, so its cutoffs are
+//! far tighter, at a false-positive rate of 2^-40; they catch only pathological output.
+//! A tripped monitor stays tripped: a source that has failed once is not trusted again.
+
+/// Repetition count test: this many identical samples in a row is a failure.
+const REPETITION_CUTOFF: u32 = 21;
+/// Adaptive proportion test window size, in samples.
+const PROPORTION_WINDOW: u32 = 512;
+/// Adaptive proportion test: this many copies of a window's first sample is a failure.
+const PROPORTION_CUTOFF: u32 = 410;
+/// Repetition count cutoff for conditioned output bytes.
+const OUTPUT_REPETITION_CUTOFF: u32 = 6;
+/// Adaptive proportion cutoff for conditioned output bytes, over the same window.
+const OUTPUT_PROPORTION_CUTOFF: u32 = 20;
+
+/// Running state of the repetition count and adaptive proportion tests.
+#[derive(Debug, Clone)]
+pub(crate) struct ContinuousHealth {
+    repetition_cutoff: u32, // Run length that trips the repetition count test
+    proportion_cutoff: u32, // Window matches that trip the adaptive proportion test
+    last: Option<u8>,       // Previous sample, for the repetition count test
+    run: u32,               // Length of the current run of identical samples
+    window_first: u8,       // First sample of the current proportion window
+    window_seen: u32,       // Samples seen in the current window, 0 if none is open
+    window_matches: u32,    // Samples in the current window equal to `window_first`
+    failed: bool,           // Set once either test trips
+}
+
+/// A monitor for raw jitter samples.
+impl Default for ContinuousHealth {
+    fn default() -> Self {
+        Self::with_cutoffs(REPETITION_CUTOFF, PROPORTION_CUTOFF)
+    }
+}
+
+impl ContinuousHealth {
+    /// A monitor for conditioned output, which should be indistinguishable from uniform.
+    pub(crate) fn for_output() -> Self {
+        Self::with_cutoffs(OUTPUT_REPETITION_CUTOFF, OUTPUT_PROPORTION_CUTOFF)
+    }
+
+    fn with_cutoffs(repetition_cutoff: u32, proportion_cutoff: u32) -> Self {
+        ContinuousHealth {
+            repetition_cutoff,
+            proportion_cutoff,
+            last: None,
+            run: 0,
+            window_first: 0,
+            window_seen: 0,
+            window_matches: 0,
+            failed: false,
+        }
+    }
+
+    /// Feeds `samples` through both tests.
+    ///
+    /// # Returns
+    /// `true` if the source is still healthy, `false` once either test has tripped.
+    pub(crate) fn check(&mut self, samples: &[u8]) -> bool {
+        for &sample in samples {
+            if self.failed {
+                break;
+            }
+            self.failed = !self.repetition_ok(sample) || !self.proportion_ok(sample);
+        }
+        !self.failed
+    }
+
+    fn repetition_ok(&mut self, sample: u8) -> bool {
+        if self.last == Some(sample) {
+            self.run += 1;
+        } else {
+            self.last = Some(sample);
+            self.run = 1;
+        }
+        self.run < self.repetition_cutoff
+    }
+
+    fn proportion_ok(&mut self, sample: u8) -> bool {
+        if self.window_seen == 0 {
+            self.window_first = sample;
+            self.window_matches = 0;
+        }
+        if sample == self.window_first {
+            self.window_matches += 1;
+        }
+        self.window_seen += 1;
+        let ok = self.window_matches < self.proportion_cutoff;
+        if self.window_seen == PROPORTION_WINDOW {
+            self.window_seen = 0;
+        }
+        ok
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repetition_and_proportion_cutoffs() {
+        let mut varied = ContinuousHealth::default();
+        let samples: Vec<u8> = (0..4096u32).map(|i| (i * 37 % 251) as u8).collect();
+        assert!(varied.check(&samples));
+
+        let mut stuck = ContinuousHealth::default();
+        assert!(stuck.check(&[9; REPETITION_CUTOFF as usize - 1]));
+        assert!(!stuck.check(&[9]));
+        assert!(!stuck.check(&samples), "a tripped monitor stays tripped");
+
+        // Never 21 in a row, but the window's first value dominates it.
+        let mut biased = ContinuousHealth::default();
+        let pattern: Vec<u8> = (0..PROPORTION_WINDOW).map(|i| if i % 5 == 4 { 1 } else { 0 }).collect();
+        assert!(!biased.check(&pattern));
+    }
+
+    #[test]
+    fn test_output_cutoffs_are_tighter() {
+        let mut output = ContinuousHealth::for_output();
+        assert!(output.check(&[1, 2, 3, 3, 3, 3, 3, 4]));
+        assert!(!output.check(&[7; OUTPUT_REPETITION_CUTOFF as usize]));
+        assert!(ContinuousHealth::default().check(&[7; OUTPUT_REPETITION_CUTOFF as usize]));
+    }
+}
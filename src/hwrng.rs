@@ -0,0 +1,60 @@
+//! Optional RDSEED entropy on x86, mixed into the pool with the `x86-hwrng` feature.
+
+#[cfg(target_arch = "x86")]
+use core::arch::x86::_rdseed32_step;
+#[cfg(target_arch = "x86_64")]
+use core::arch::x86_64::_rdseed32_step;
+
+/// RDSEED may transiently run dry; Intel recommends retrying a small number of times.
+const RDSEED_RETRIES: usize = 10;
+
+/// Reads `len` bytes from the CPU's RDSEED instruction, if the CPU supports it.
+///
+/// Returns `None` when RDSEED is unavailable or keeps failing. The output is only ever
+/// mixed into the entropy pool alongside OS entropy, never used on its own.
+pub(crate) fn rdseed_bytes(len: usize) -> Option<Vec<u8>> {
+    if !is_x86_feature_detected!("rdseed") {
+        return None;
+    }
+    let mut out = Vec::with_capacity(len + 4);
+    while out.len() < len {
+        // SAFETY: RDSEED support was detected at runtime above.
+        let word = unsafe { rdseed_word()? };
+        out.extend_from_slice(&word.to_le_bytes());
+    }
+    out.truncate(len);
+    Some(out)
+}
+
+#[target_feature(enable = "rdseed")]
+unsafe fn rdseed_word() -> Option<u32> {
+    let mut word = 0u32;
+    for _ in 0..RDSEED_RETRIES {
+        if _rdseed32_step(&mut word) == 1 {
+            return Some(word);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrueRandom;
+
+    #[test]
+    fn test_hardware_source_contributes_when_present() {
+        if !is_x86_feature_detected!("rdseed") {
+            assert!(rdseed_bytes(16).is_none());
+            return;
+        }
+        let hw = rdseed_bytes(16).expect("RDSEED available but failing");
+        assert_eq!(hw.len(), 16);
+
+        // Jitter (10) + simulated (16) + hardware (16) bytes are absorbed per call.
+        let mut trng = TrueRandom::new();
+        let before = trng.entropy_pool.len();
+        trng.generate(32);
+        assert_eq!(trng.entropy_pool.len(), before + 42);
+    }
+}
@@ -0,0 +1,171 @@
+//! Hybrid KEM running this crate's scheme alongside an X25519 exchange.
+//!
+//! The two shared secrets are combined with SHA3-256 together with both ciphertexts and the
+//! recipient's X25519 key, so the result stays secret unless an attacker breaks both
+//! components, and changing either ciphertext changes the secret. Deploy this rather than the
+//! toy KEM alone so a break of the post-quantum half leaves classical security intact.
+
+use crate::{decapsulate, encapsulate, fill_random, keypair, Ciphertext, PublicKey, SecretKey, SharedSecret};
+use sha3::digest::Update;
+use sha3::{Digest, Sha3_256};
+use x25519_dalek::StaticSecret;
+use zeroize::Zeroize;
+
+/// Public key of the hybrid KEM: a `PublicKey` plus an X25519 public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HybridPublicKey {
+    kem: PublicKey,                  // Post-quantum half
+    x25519: x25519_dalek::PublicKey, // Classical half
+}
+
+/// Secret key of the hybrid KEM; both halves are zeroized on drop.
+pub struct HybridSecretKey {
+    kem: SecretKey,          // Post-quantum half
+    x25519: StaticSecret,    // Classical half
+    x25519_public: [u8; 32], // Matching X25519 public key, bound into the combined secret
+}
+
+/// Ciphertext of the hybrid KEM: a `Ciphertext` plus an ephemeral X25519 public key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HybridCiphertext {
+    kem: Ciphertext,  // Post-quantum half
+    x25519: [u8; 32], // Sender's ephemeral X25519 public key
+}
+
+impl HybridPublicKey {
+    /// Returns the post-quantum half of the key.
+    pub fn kem(&self) -> &PublicKey {
+        &self.kem
+    }
+
+    /// Returns the X25519 half of the key as its 32-byte encoding.
+    pub fn x25519(&self) -> [u8; 32] {
+        self.x25519.to_bytes()
+    }
+}
+
+impl HybridCiphertext {
+    /// Returns the post-quantum half of the ciphertext.
+    pub fn kem(&self) -> &Ciphertext {
+        &self.kem
+    }
+
+    /// Returns the sender's ephemeral X25519 public key.
+    pub fn x25519(&self) -> [u8; 32] {
+        self.x25519
+    }
+}
+
+/// Generates a hybrid keypair.
+///
+/// # Returns
+/// A tuple `(HybridPublicKey, HybridSecretKey)`.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{hybrid_keypair, hybrid_encapsulate, hybrid_decapsulate};
+/// let (pk, sk) = hybrid_keypair();
+/// let (ct, ss) = hybrid_encapsulate(&pk);
+/// assert_eq!(hybrid_decapsulate(&ct, &sk).as_bytes(), ss.as_bytes());
+/// ```
+pub fn hybrid_keypair() -> (HybridPublicKey, HybridSecretKey) {
+    let (kem_pk, kem_sk) = keypair();
+    let x25519 = random_x25519();
+    let x25519_pk = x25519_dalek::PublicKey::from(&x25519);
+
+    let pk = HybridPublicKey {
+        kem: kem_pk,
+        x25519: x25519_pk,
+    };
+    let sk = HybridSecretKey {
+        kem: kem_sk,
+        x25519,
+        x25519_public: x25519_pk.to_bytes(),
+    };
+    (pk, sk)
+}
+
+/// Encapsulates a combined shared secret to a hybrid public key.
+///
+/// # Arguments
+/// * `pk` - The recipient's `HybridPublicKey`.
+///
+/// # Returns
+/// A tuple `(HybridCiphertext, SharedSecret)`.
+pub fn hybrid_encapsulate(pk: &HybridPublicKey) -> (HybridCiphertext, SharedSecret) {
+    let (kem_ct, kem_ss) = encapsulate(&pk.kem);
+    let ephemeral = random_x25519();
+    let ephemeral_pk = x25519_dalek::PublicKey::from(&ephemeral).to_bytes();
+    let dh = ephemeral.diffie_hellman(&pk.x25519);
+
+    let ct = HybridCiphertext {
+        kem: kem_ct,
+        x25519: ephemeral_pk,
+    };
+    let ss = combine(&kem_ss, dh.as_bytes(), &ct, &pk.x25519.to_bytes());
+    (ct, ss)
+}
+
+/// Recovers the combined shared secret from a hybrid ciphertext.
+///
+/// Like `decapsulate`, a modified ciphertext is not rejected: it yields an unrelated secret,
+/// so the first authenticated message under it fails.
+///
+/// # Arguments
+/// * `ct` - The `HybridCiphertext` to decapsulate.
+/// * `sk` - The recipient's `HybridSecretKey`.
+///
+/// # Returns
+/// The combined `SharedSecret`.
+pub fn hybrid_decapsulate(ct: &HybridCiphertext, sk: &HybridSecretKey) -> SharedSecret {
+    let kem_ss = decapsulate(&ct.kem, &sk.kem);
+    let dh = sk.x25519.diffie_hellman(&x25519_dalek::PublicKey::from(ct.x25519));
+    combine(&kem_ss, dh.as_bytes(), ct, &sk.x25519_public)
+}
+
+/// Draws an X25519 secret from the default randomness source; it is zeroized when dropped.
+fn random_x25519() -> StaticSecret {
+    let mut seed = [0u8; 32];
+    fill_random(&mut seed);
+    let secret = StaticSecret::from(seed);
+    seed.zeroize();
+    secret
+}
+
+/// Hashes both secrets with both ciphertexts and the recipient's X25519 key.
+///
+/// The classical ciphertext and key are bound in, as X-Wing does, because X25519 alone does
+/// not tie its shared secret to the exact public values used.
+fn combine(kem_ss: &SharedSecret, dh: &[u8; 32], ct: &HybridCiphertext, recipient: &[u8; 32]) -> SharedSecret {
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, b"ruption-hybrid-x25519-v1");
+    Update::update(&mut hasher, kem_ss.as_bytes());
+    Update::update(&mut hasher, dh);
+    Update::update(&mut hasher, &ct.kem.to_bytes());
+    Update::update(&mut hasher, &ct.x25519);
+    Update::update(&mut hasher, recipient);
+    SharedSecret::new(hasher.finalize().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hybrid_round_trip_and_classical_tamper() {
+        let (pk, sk) = hybrid_keypair();
+        let (ct, ss) = hybrid_encapsulate(&pk);
+        assert_eq!(ss.len(), 32);
+        assert!(hybrid_decapsulate(&ct, &sk) == ss);
+        assert_eq!(pk.x25519(), sk.x25519_public);
+
+        // Only the classical half changes; the KEM half still decapsulates correctly.
+        let mut tampered = ct.clone();
+        tampered.x25519[0] ^= 1;
+        assert!(decapsulate(tampered.kem(), &sk.kem) == decapsulate(ct.kem(), &sk.kem));
+        assert!(hybrid_decapsulate(&tampered, &sk) != ss);
+
+        let (_other_pk, other_sk) = hybrid_keypair();
+        assert!(hybrid_decapsulate(&ct, &other_sk) != ss);
+    }
+}
@@ -0,0 +1,174 @@
+//! Named collections of keys, with a consistency check for keys loaded from storage.
+
+use crate::{public_digest, PublicKey, SecretKey};
+use std::collections::HashSet;
+use std::fmt;
+
+/// A collection of named keys, e.g. as loaded from a key store.
+///
+/// Entries are added as-is; call `verify` after loading to catch corruption.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, KeyRing};
+/// let (pk, sk) = keypair();
+/// let mut ring = KeyRing::new();
+/// ring.add("alice", pk, Some(sk));
+/// assert!(ring.verify().is_ok());
+/// ```
+#[derive(Default)]
+pub struct KeyRing {
+    entries: Vec<KeyEntry>, // In insertion order; ids are not deduplicated
+}
+
+struct KeyEntry {
+    id: String,                // Caller-chosen key id
+    public: PublicKey,         // Public half, always present
+    secret: Option<SecretKey>, // Secret half, absent for other parties' keys
+}
+
+/// A problem found by `KeyRing::verify`, naming the affected key id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RingError {
+    /// The public key has the wrong dimensions.
+    MalformedPublicKey(String),
+    /// The secret key has the wrong dimensions.
+    MalformedSecretKey(String),
+    /// The public key is not the one derived from the secret key.
+    KeyMismatch(String),
+    /// More than one entry uses this id.
+    DuplicateId(String),
+}
+
+impl fmt::Display for RingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RingError::MalformedPublicKey(id) => write!(f, "key {id:?}: malformed public key"),
+            RingError::MalformedSecretKey(id) => write!(f, "key {id:?}: malformed secret key"),
+            RingError::KeyMismatch(id) => write!(f, "key {id:?}: secret key does not match public key"),
+            RingError::DuplicateId(id) => write!(f, "key id {id:?} is used more than once"),
+        }
+    }
+}
+
+impl std::error::Error for RingError {}
+
+impl KeyRing {
+    /// Creates an empty key ring.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key under `id`; pass `None` for keys whose secret half is held elsewhere.
+    pub fn add(&mut self, id: impl Into<String>, public: PublicKey, secret: Option<SecretKey>) {
+        self.entries.push(KeyEntry {
+            id: id.into(),
+            public,
+            secret,
+        });
+    }
+
+    /// Returns the public key of the first entry with this id.
+    pub fn public_key(&self, id: &str) -> Option<&PublicKey> {
+        self.entries.iter().find(|e| e.id == id).map(|e| &e.public)
+    }
+
+    /// Returns the number of entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the ring holds no keys.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Checks every entry and reports all problems found, not just the first.
+    ///
+    /// Each public and secret key must have the expected dimensions, each public key must be
+    /// the one its secret key derives (`PublicKey::from_secret`), and no id may appear twice.
+    ///
+    /// # Returns
+    /// `Ok(())` if the ring is consistent, otherwise every `RingError` in entry order.
+    pub fn verify(&self) -> Result<(), Vec<RingError>> {
+        let mut problems = Vec::new();
+        let mut seen = HashSet::new();
+        let mut reported = HashSet::new();
+        for entry in &self.entries {
+            if !seen.insert(entry.id.as_str()) && reported.insert(entry.id.as_str()) {
+                problems.push(RingError::DuplicateId(entry.id.clone()));
+            }
+
+            let public_ok = entry.public.is_well_formed();
+            if !public_ok {
+                problems.push(RingError::MalformedPublicKey(entry.id.clone()));
+            }
+            if let Some(secret) = &entry.secret {
+                if !secret.is_well_formed() || (public_ok && secret.params() != entry.public.params()) {
+                    problems.push(RingError::MalformedSecretKey(entry.id.clone()));
+                } else if public_ok && !keys_match(&entry.public, secret) {
+                    problems.push(RingError::KeyMismatch(entry.id.clone()));
+                }
+            }
+        }
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// Checks that `pk` is the public key `sk` determines and the digest `sk` stores for it.
+///
+/// The rederived key covers the secret components; the digest is stored alongside them and
+/// bound into every shared secret, so it is compared separately.
+fn keys_match(pk: &PublicKey, sk: &SecretKey) -> bool {
+    PublicKey::from_secret(sk) == *pk && sk.public_digest == public_digest(pk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keypair;
+
+    #[test]
+    fn test_verify_reports_every_problem() {
+        let mut ring = KeyRing::new();
+        for id in ["alice", "bob", "carol"] {
+            let (pk, sk) = keypair();
+            ring.add(id, pk, Some(sk));
+        }
+        assert!(ring.verify().is_ok());
+
+        let (mut corrupted, sk) = keypair();
+        corrupted._lattice_matrix[3].pop();
+        ring.add("dave", corrupted, Some(sk));
+        let (pk, _sk) = keypair();
+        ring.add("bob", pk, None);
+        let (pk, mut truncated) = keypair();
+        truncated._code_secret.truncate(1);
+        ring.add("erin", pk, Some(truncated));
+        // Intact digest and sizes, but changed secret bytes: only a full comparison notices.
+        let (pk, mut flipped) = keypair();
+        flipped._lattice_secret[0] = u8::from(flipped._lattice_secret[0] == 0);
+        flipped._code_secret[0] ^= 1;
+        ring.add("frank", pk, Some(flipped));
+        // Intact secret components, but a stored digest for some other key.
+        let (pk, mut misdigested) = keypair();
+        misdigested.public_digest[0] ^= 1;
+        ring.add("grace", pk, Some(misdigested));
+
+        assert_eq!(
+            ring.verify(),
+            Err(vec![
+                RingError::MalformedPublicKey("dave".into()),
+                RingError::DuplicateId("bob".into()),
+                RingError::MalformedSecretKey("erin".into()),
+                RingError::KeyMismatch("frank".into()),
+                RingError::KeyMismatch("grace".into()),
+            ])
+        );
+        assert_eq!(ring.len(), 8);
+    }
+}
@@ -1,53 +1,754 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use getrandom::getrandom;
-use sha3::{Digest, Sha3_256, Shake256};
-use sha3::digest::{Update, ExtendableOutput};
+use rand_core::RngCore;
+#[cfg(feature = "std")]
+use rand_core::CryptoRng;
+use sha3::{Digest, Sha3_256, Shake128, Shake256};
+use sha3::digest::{Update, ExtendableOutput, XofReader};
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
+#[cfg(feature = "std")]
 use std::io::Read;
+#[cfg(feature = "std")]
 use std::thread;
+#[cfg(feature = "std")]
 use std::time::{Instant, Duration};
 
+use text::to_hex;
+
+mod aead;
+mod blind;
+mod cache;
+mod channel;
+mod codec;
+mod envelope;
+mod error;
+#[cfg(feature = "std")]
+mod global;
+mod handshake;
+#[cfg(feature = "std")]
+mod health;
+#[cfg(feature = "hybrid")]
+mod hybrid;
+#[cfg(all(feature = "x86-hwrng", any(target_arch = "x86", target_arch = "x86_64")))]
+mod hwrng;
+#[cfg(feature = "std")]
+mod keyring;
+mod lwe;
+mod memlock;
+mod message;
+mod params;
+mod password;
+mod pke;
+#[cfg(feature = "std")]
+mod quality;
+mod schedule;
+#[cfg(feature = "serde")]
+mod serde_impls;
+#[cfg(feature = "std")]
+mod shared;
+mod siv;
+#[cfg(feature = "sponge-aead")]
+mod sponge;
+#[cfg(feature = "testing")]
+mod testing;
+mod text;
+#[cfg(feature = "std")]
+mod trng_builder;
+
+pub use blind::BlindedKey;
+pub use cache::CachingDecapsulator;
+pub use channel::MessageChannel;
+pub use envelope::{decrypt_from_many, open_group_key, GroupKeyWrap};
+#[cfg(feature = "std")]
+pub use envelope::{encrypt_to_many, seal_group_key};
+pub use error::QuantumError;
+#[cfg(feature = "std")]
+pub use global::{global_rng, GlobalRng};
+pub use handshake::{ConfirmMessage, Handshake, InitiatorMessage, Responder, ResponderMessage, Session};
+#[cfg(feature = "hybrid")]
+pub use hybrid::{
+    hybrid_decapsulate, hybrid_encapsulate, hybrid_keypair, HybridCiphertext, HybridPublicKey, HybridSecretKey,
+};
+#[cfg(feature = "std")]
+pub use keyring::{KeyRing, RingError};
+pub use message::decrypt;
+#[cfg(feature = "std")]
+pub use message::{decrypt_stream, encrypt, encrypt_stream};
+pub use params::{KemSizes, Params};
+#[cfg(feature = "std")]
+pub use params::measure_decaps_failure_rate;
+pub use password::{derive_key_from_password, SALT_LEN};
+#[cfg(feature = "std")]
+pub use password::generate_salt;
+pub use pke::decrypt_from;
+#[cfg(feature = "std")]
+pub use pke::encrypt_to;
+#[cfg(feature = "std")]
+pub use quality::{entropy_quality_report, QualityReport};
+pub use schedule::open_key_schedule;
+#[cfg(feature = "std")]
+pub use schedule::seal_key_schedule;
+#[cfg(feature = "std")]
+pub use shared::SharedRandom;
+pub use siv::{open_siv, seal_siv};
+#[cfg(feature = "sponge-aead")]
+pub use sponge::{open_sponge, seal_sponge};
+#[cfg(feature = "std")]
+pub use trng_builder::{EntropySource, RngError, TrueRandomBuilder};
+#[cfg(feature = "testing")]
+pub use testing::{
+    decapsulate_debug, generate_test_vector, verify_test_vectors, DecapsDiagnostics, SessionTrace, TestVector,
+    VectorOutcome, VerificationReport,
+};
+
 // Toy parameters for demonstration; scale up for real security
 const LATTICE_DIM: usize = 256;  // Lattice dimension (use 2048+ for billion-qubit resistance)
 const CODE_LENGTH: usize = 512;  // Code length (use 8192+ for extreme security)
+const _: () = assert!(LATTICE_DIM > 0 && CODE_LENGTH.is_multiple_of(8), "code secret is CODE_LENGTH / 8 bytes");
+const CBD_ETA: i16 = 2;          // Lattice secret coefficients lie in -CBD_ETA..=CBD_ETA
+const CIPHERTEXT_MAGIC: &[u8; 4] = b"RQE1"; // Leading bytes of every encoded Ciphertext
+const CIPHERTEXT_VERSION: u8 = 1;           // Ciphertext wire format version; bump on any layout change
+const CIPHERTEXT_HEADER_LEN: usize = 5;     // Magic plus version byte
+#[cfg(feature = "std")]
+const JITTER_ROUNDS: usize = 10; // Default jitter samples collected per TrueRandom draw
+#[cfg(feature = "std")]
+const POOL_CAP: usize = 256;     // TrueRandom pool length above which each draw condenses it
+
+/// Length in bytes of every `SharedSecret` (the SHA3-256 output), for all parameter sets.
+///
+/// `Params::sizes` reports the same value per parameter set.
+pub const SHARED_SECRET_LEN: usize = 32;
 
 /// Public key for the quantum-secure encryption scheme.
 ///
 /// Combines lattice-based and code-based cryptographic components.
 /// This is a simplified representation; production use requires proper LWE and McEliece implementations.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PublicKey {
-    _lattice_matrix: Vec<Vec<u8>>, // Simplified lattice public key (matrix), unused in toy version
+    _lattice_matrix: Vec<Vec<u8>>, // Toy LWE rows: a row of the matrix A, then that row of B = A·S + E
     _code_generator: Vec<u8>,      // Simplified code-based generator, unused in toy version
 }
 
+impl PublicKey {
+    /// Builds a public key from externally computed components.
+    ///
+    /// For interop testing against reference implementations and custom key-generation backends.
+    ///
+    /// # Arguments
+    /// * `lattice_matrix` - `lattice_dim` rows of `lattice_dim + 16` bytes each: a row of the
+    ///   LWE matrix `A`, then that row of `B = A·S + E` as eight big-endian `u16` entries.
+    /// * `code_generator` - `code_length` bytes, a positive multiple of 8.
+    ///
+    /// # Returns
+    /// The `PublicKey`, or `QuantumError::InvalidLength` if the shapes fit no `Params`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::PublicKey;
+    /// let matrix = vec![vec![0u8; 256 + 16]; 256];
+    /// assert!(PublicKey::from_components(matrix, vec![0u8; 512]).is_ok());
+    /// assert!(PublicKey::from_components(Vec::new(), vec![0u8; 512]).is_err());
+    /// ```
+    pub fn from_components(lattice_matrix: Vec<Vec<u8>>, code_generator: Vec<u8>) -> Result<Self, QuantumError> {
+        let pk = PublicKey {
+            _lattice_matrix: lattice_matrix,
+            _code_generator: code_generator,
+        };
+        if !pk.is_well_formed() {
+            return Err(QuantumError::InvalidLength);
+        }
+        Ok(pk)
+    }
+
+    /// Derives the public key that belongs to `sk`.
+    ///
+    /// The matrix `A` and the generator are a SHAKE256 expansion of the secret components, and
+    /// each row of `A` is followed by its row of `B = A·S + E` for the LWE secret `S`, so the
+    /// public key is a deterministic function of the secret; `keypair` builds its public key
+    /// this way. Toy construction: real schemes expand `A` from a public seed instead.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, PublicKey};
+    /// let (pk, sk) = keypair();
+    /// assert_eq!(PublicKey::from_secret(&sk), pk);
+    /// ```
+    pub fn from_secret(sk: &SecretKey) -> PublicKey {
+        let params = sk.params();
+        let lattice_dim = params.lattice_dim();
+        let mut xof = Shake256::default();
+        xof.update(b"ruption-public-from-secret-v1");
+        aead::absorb_framed(&mut xof, &sk._lattice_secret);
+        aead::absorb_framed(&mut xof, &sk._code_secret);
+        let mut reader = xof.finalize_xof();
+
+        let mut squeeze = |len: usize| {
+            let mut out = vec![0u8; len];
+            XofReader::read(&mut reader, &mut out);
+            out
+        };
+        let mut lattice_matrix: Vec<Vec<u8>> = (0..lattice_dim).map(|_| squeeze(lattice_dim)).collect();
+        let code_generator = squeeze(params.code_length());
+        lwe::append_public_columns(&mut lattice_matrix, &sk._lattice_secret);
+        PublicKey {
+            _lattice_matrix: lattice_matrix,
+            _code_generator: code_generator,
+        }
+    }
+
+    /// Encodes the key for storage or transmission.
+    ///
+    /// The layout is the number of matrix rows as a big-endian `u32`, then each row and then
+    /// the code generator, each preceded by its `u32` length.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, PublicKey};
+    /// let (pk, _sk) = keypair();
+    /// assert_eq!(PublicKey::from_bytes(&pk.to_bytes()).unwrap(), pk);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let rows: usize = self._lattice_matrix.iter().map(|row| 4 + row.len()).sum();
+        let mut out = Vec::with_capacity(8 + rows + self._code_generator.len());
+        codec::put_u32(&mut out, self._lattice_matrix.len());
+        for row in &self._lattice_matrix {
+            codec::put_bytes(&mut out, row);
+        }
+        codec::put_bytes(&mut out, &self._code_generator);
+        out
+    }
+
+    /// Decodes a key encoded by `to_bytes`.
+    ///
+    /// # Returns
+    /// The `PublicKey`, `QuantumError::Deserialize` if the input is truncated or has trailing
+    /// bytes, or `QuantumError::InvalidLength` if a component has the wrong size.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QuantumError> {
+        let mut rest = bytes;
+        let rows = codec::take_u32(&mut rest)?;
+        let mut lattice_matrix = Vec::new();
+        for _ in 0..rows {
+            lattice_matrix.push(codec::take_prefixed(&mut rest)?.to_vec());
+        }
+        let code_generator = codec::take_prefixed(&mut rest)?.to_vec();
+        if !rest.is_empty() {
+            return Err(QuantumError::Deserialize);
+        }
+        PublicKey::from_components(lattice_matrix, code_generator)
+    }
+
+    /// Returns the parameter set this key was generated for.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, Params};
+    /// let (pk, sk) = keypair();
+    /// assert_eq!(pk.params(), Params::toy());
+    /// assert_eq!(sk.params(), pk.params());
+    /// ```
+    pub fn params(&self) -> Params {
+        Params::from_dims(self._lattice_matrix.len(), self._code_generator.len())
+    }
+
+    /// Returns a short, stable identifier for this key, for logs and key-management UIs.
+    ///
+    /// It is the first 16 bytes of SHA3-256 over `to_bytes()`, so it is the same on every run
+    /// and platform for the same key. Sixteen bytes identify keys, but do not authenticate
+    /// them: compare full keys before trusting one.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::keypair;
+    /// let (pk, _sk) = keypair();
+    /// assert_eq!(pk.fingerprint(), pk.clone().fingerprint());
+    /// assert_eq!(pk.fingerprint_hex().len(), 32);
+    /// ```
+    pub fn fingerprint(&self) -> [u8; 16] {
+        let digest = Sha3_256::digest(self.to_bytes());
+        let mut fingerprint = [0u8; 16];
+        fingerprint.copy_from_slice(&digest[..16]);
+        fingerprint
+    }
+
+    /// Returns `fingerprint()` as 32 lowercase hex digits.
+    pub fn fingerprint_hex(&self) -> String {
+        to_hex(&self.fingerprint())
+    }
+
+    /// Returns `true` if every row holds `A` and `B` for a square `A` and the shapes fit a valid `Params`.
+    fn is_well_formed(&self) -> bool {
+        let rows = self._lattice_matrix.len();
+        self.params().is_valid() && self._lattice_matrix.iter().all(|row| row.len() == rows + lwe::ROW_EXTRA)
+    }
+}
+
 /// Secret key for the quantum-secure encryption scheme.
 ///
 /// Contains private data for lattice and code-based decryption.
 /// This is a toy version; scale parameters for real-world security.
+///
+/// The key material is zeroized on drop, including when a panic unwinds past the key. With the
+/// `mlock` feature it is also locked in RAM on Unix, so it is never written to swap.
+/// Under `panic = "abort"` no destructors run, so a panicking process exits with the key
+/// still in memory; rely on the OS reclaiming it, or avoid aborting while keys are live.
 pub struct SecretKey {
-    _lattice_secret: Vec<u8>,      // Lattice private key, unused in toy version
+    _lattice_secret: Vec<u8>,      // Lattice private key: CBD coefficients as i8 bytes, seeding the LWE secret
     _code_secret: Vec<u8>,         // Code private key, unused in toy version
+    public_digest: [u8; 32],       // Digest of the matching public key, bound into shared secrets
+}
+
+impl SecretKey {
+    /// Performs a quick sanity check that the secret material is not degenerate.
+    ///
+    /// Returns `false` if any single value dominates a component beyond what its distribution
+    /// allows (e.g. an all-zero key from a failed RNG), or if a lattice coefficient is out of
+    /// range. Histograms are built without secret-dependent indexing or branching.
+    /// This catches catastrophic failures only; it does not prove the key is strong.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::keypair;
+    /// let (_pk, sk) = keypair();
+    /// assert!(sk.looks_healthy());
+    /// ```
+    pub fn looks_healthy(&self) -> bool {
+        let code_len = self._code_secret.len();
+        let lattice_len = self._lattice_secret.len();
+        // Uniform code bytes: one value reaching 1/8 of the component is rarer than 1 in 2^28.
+        let code_ok = most_common_count(&self._code_secret) * 8 <= code_len;
+        // CBD(2) coefficients are zero 3/8 of the time; 9/16 is over six standard deviations out.
+        let lattice_ok = most_common_count(&self._lattice_secret) * 16 <= lattice_len * 9;
+        code_len > 0 && lattice_len > 0 && code_ok && lattice_ok && self.coefficients_in_range(CBD_ETA)
+    }
+
+    /// Checks that every lattice secret coefficient lies in `-bound..=bound`.
+    ///
+    /// Coefficients are sampled from a centered binomial distribution with `η = 2`, so a
+    /// correctly generated key passes with `bound = 2`. LWE security depends on the secret
+    /// being this narrow. The check runs in constant time.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::keypair;
+    /// let (_pk, sk) = keypair();
+    /// assert!(sk.coefficients_in_range(2));
+    /// ```
+    pub fn coefficients_in_range(&self, bound: i16) -> bool {
+        let mut in_range = 1u8;
+        for &byte in &self._lattice_secret {
+            let coefficient = byte as i8 as i16;
+            // Both differences are non-negative exactly when the coefficient is in range.
+            let below = ((bound - coefficient) >> 15) as u8 & 1;
+            let above = ((bound + coefficient) >> 15) as u8 & 1;
+            in_range &= 1 ^ (below | above);
+        }
+        in_range == 1
+    }
+
+    /// Returns the parameter set this key was generated for.
+    pub fn params(&self) -> Params {
+        Params::from_dims(self._lattice_secret.len(), self._code_secret.len() * 8)
+    }
+
+    /// Returns `true` if the component shapes fit a valid `Params`.
+    fn is_well_formed(&self) -> bool {
+        self.params().is_valid()
+    }
+
+    /// Encodes the key for storage; the buffer is wiped when dropped.
+    ///
+    /// The layout is the lattice secret and the code secret, each preceded by its length as a
+    /// big-endian `u32`, then the 32-byte digest of the matching public key.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, encapsulate, decapsulate, SecretKey};
+    /// let (pk, sk) = keypair();
+    /// let restored = SecretKey::from_bytes(&sk.to_bytes()).unwrap();
+    /// let (ct, ss) = encapsulate(&pk);
+    /// assert_eq!(decapsulate(&ct, &restored).as_bytes(), ss.as_bytes());
+    /// ```
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut out = Zeroizing::new(Vec::with_capacity(8 + self._lattice_secret.len() + self._code_secret.len() + 32));
+        codec::put_bytes(&mut out, &self._lattice_secret);
+        codec::put_bytes(&mut out, &self._code_secret);
+        out.extend_from_slice(&self.public_digest);
+        out
+    }
+
+    /// Decodes a key encoded by `to_bytes`.
+    ///
+    /// # Returns
+    /// The `SecretKey`, `QuantumError::Deserialize` if the input is truncated or has trailing
+    /// bytes, or `QuantumError::InvalidLength` if a component has the wrong size.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QuantumError> {
+        let mut rest = bytes;
+        let lattice_secret = codec::take_prefixed(&mut rest)?;
+        let code_secret = codec::take_prefixed(&mut rest)?;
+        let digest = codec::take_bytes(&mut rest, 32)?;
+        if !rest.is_empty() {
+            return Err(QuantumError::Deserialize);
+        }
+        let mut public_digest = [0u8; 32];
+        public_digest.copy_from_slice(digest);
+        let sk = SecretKey {
+            _lattice_secret: lattice_secret.to_vec(),
+            _code_secret: code_secret.to_vec(),
+            public_digest,
+        };
+        if !sk.is_well_formed() {
+            return Err(QuantumError::InvalidLength);
+        }
+        memlock::lock(&sk._lattice_secret);
+        memlock::lock(&sk._code_secret);
+        Ok(sk)
+    }
+
+    /// Returns a deterministic public value derived from this secret key, for `shared_value`.
+    ///
+    /// Toy only: see `shared_value` for why this agreement offers no secrecy.
+    pub fn public_share(&self) -> Vec<u8> {
+        let mut hasher = Sha3_256::new();
+        Update::update(&mut hasher, b"ruption-public-share-v1");
+        Update::update(&mut hasher, &self._lattice_secret);
+        Update::update(&mut hasher, &self._code_secret);
+        hasher.finalize().to_vec()
+    }
+}
+
+/// Hashes a public key into the value both sides bind into the shared secret.
+///
+/// `SecretKey` stores this digest of its own public key (as ML-KEM stores `H(pk)`), so a
+/// ciphertext decapsulated with another key pair's secret yields an unrelated secret.
+fn public_digest(pk: &PublicKey) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, b"ruption-kem-public-key-v1");
+    for row in &pk._lattice_matrix {
+        Update::update(&mut hasher, row);
+    }
+    Update::update(&mut hasher, &pk._code_generator);
+    hasher.finalize().into()
+}
+
+/// Returns how often the most frequent byte value occurs, without secret-dependent branches.
+fn most_common_count(bytes: &[u8]) -> usize {
+    let mut counts = [0u32; 256];
+    for &byte in bytes {
+        for (value, count) in counts.iter_mut().enumerate() {
+            *count += byte.ct_eq(&(value as u8)).unwrap_u8() as u32;
+        }
+    }
+    counts.iter().fold(0u32, |max, &c| max.max(c)) as usize
+}
+
+/// Samples `n` coefficients from the centered binomial distribution with `η = 2`.
+///
+/// Each coefficient is `(a0 + a1) - (b0 + b1)` over four random bits, so two come from each
+/// byte of `random`. Coefficients are stored as two's-complement `i8` bytes.
+fn sample_cbd(random: &[u8], n: usize) -> Vec<u8> {
+    let mut coefficients = Vec::with_capacity(n);
+    for i in 0..n {
+        let bits = random[i / 2] >> (4 * (i % 2));
+        let a = (bits & 1) + ((bits >> 1) & 1);
+        let b = ((bits >> 2) & 1) + ((bits >> 3) & 1);
+        coefficients.push((a as i8 - b as i8) as u8);
+    }
+    coefficients
 }
 
 /// Ciphertext produced during key encapsulation.
 ///
 /// Holds encrypted data from both lattice and code-based components.
 /// In practice, this would result from proper cryptographic operations.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Ciphertext {
-    lattice_cipher: Vec<u8>,      // Lattice-based ciphertext
-    code_cipher: Vec<u8>,         // Code-based ciphertext
+    lattice_cipher: Vec<u8>,      // Toy LWE ciphertext C1 ‖ C2 as big-endian u16 entries
+    code_cipher: Vec<u8>,         // Code-based placeholder: random bytes that hide nothing yet
+}
+
+impl Ciphertext {
+    /// Builds a ciphertext from its lattice and code components.
+    ///
+    /// Useful for feeding known ciphertexts (e.g. golden test vectors) into `decapsulate`.
+    ///
+    /// # Returns
+    /// The `Ciphertext`, or `QuantumError::InvalidLength` if the component sizes fit no `Params`.
+    pub fn from_parts(lattice_cipher: Vec<u8>, code_cipher: Vec<u8>) -> Result<Self, QuantumError> {
+        let ct = Ciphertext {
+            lattice_cipher,
+            code_cipher,
+        };
+        if !ct.params().is_valid() {
+            return Err(QuantumError::InvalidLength);
+        }
+        Ok(ct)
+    }
+
+    /// Returns the parameter set of the key this ciphertext was encapsulated to.
+    pub fn params(&self) -> Params {
+        Params::from_dims(lwe::dim_for_cipher_len(self.lattice_cipher.len()), self.code_cipher.len())
+    }
+
+    /// Encodes the ciphertext canonically.
+    ///
+    /// The layout is the magic `b"RQE1"` and a format version byte, then the lattice
+    /// component and the code component, each preceded by its length as a big-endian `u32`.
+    /// Every ciphertext has exactly one encoding, so raw bytes can be hashed or compared safely.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, encapsulate, Ciphertext};
+    /// let (pk, _sk) = keypair();
+    /// let (ct, _ss) = encapsulate(&pk);
+    /// let bytes = ct.to_bytes();
+    /// assert!(Ciphertext::is_canonical(&bytes));
+    /// assert_eq!(Ciphertext::from_bytes_strict(&bytes).unwrap().to_bytes(), bytes);
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let len = CIPHERTEXT_HEADER_LEN + 8 + self.lattice_cipher.len() + self.code_cipher.len();
+        let mut out = Vec::with_capacity(len);
+        out.extend_from_slice(CIPHERTEXT_MAGIC);
+        out.push(CIPHERTEXT_VERSION);
+        codec::put_bytes(&mut out, &self.lattice_cipher);
+        codec::put_bytes(&mut out, &self.code_cipher);
+        out
+    }
+
+    /// Decodes a ciphertext, accepting only the canonical encoding from `to_bytes`.
+    ///
+    /// # Returns
+    /// The `Ciphertext`, `QuantumError::Deserialize` if the magic or version is not one this
+    /// crate writes (including a newer version) or the framing is malformed or followed by
+    /// trailing bytes, or `QuantumError::InvalidLength` if a component has the wrong size.
+    pub fn from_bytes_strict(bytes: &[u8]) -> Result<Self, QuantumError> {
+        let mut rest = bytes;
+        let header = codec::take_bytes(&mut rest, CIPHERTEXT_HEADER_LEN)?;
+        if header[..4] != CIPHERTEXT_MAGIC[..] || header[4] != CIPHERTEXT_VERSION {
+            return Err(QuantumError::Deserialize);
+        }
+        let lattice_cipher = codec::take_prefixed(&mut rest)?;
+        let code_cipher = codec::take_prefixed(&mut rest)?;
+        if !rest.is_empty() {
+            return Err(QuantumError::Deserialize);
+        }
+        Ciphertext::from_parts(lattice_cipher.to_vec(), code_cipher.to_vec())
+    }
+
+    /// Decodes a ciphertext encoded by `to_bytes`; the same as `from_bytes_strict`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, QuantumError> {
+        Ciphertext::from_bytes_strict(bytes)
+    }
+
+    /// Returns `true` if `bytes` is the canonical encoding of some ciphertext.
+    pub fn is_canonical(bytes: &[u8]) -> bool {
+        Ciphertext::from_bytes_strict(bytes).is_ok_and(|ct| ct.to_bytes() == bytes)
+    }
 }
 
 /// Shared secret derived during encapsulation.
 ///
 /// Used for symmetric encryption or key derivation, wrapped for type safety.
+/// Zeroized on drop, with the same unwinding and `panic = "abort"` behavior as `SecretKey`.
 pub struct SharedSecret(Vec<u8>);
 
 impl SharedSecret {
+    /// Wraps freshly derived secret bytes, locking them in memory with the `mlock` feature.
+    fn new(bytes: Vec<u8>) -> Self {
+        memlock::lock(&bytes);
+        SharedSecret(bytes)
+    }
+
     /// Returns a reference to the shared secret bytes.
     ///
     /// Useful for comparing or using the secret in encryption.
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
+
+    /// Returns the length of the secret in bytes, always `SHARED_SECRET_LEN`.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the secret has no bytes, which never happens for a real secret.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Derives the implicit 96-bit nonce for message number `counter`.
+    ///
+    /// A nonce key is derived from the secret, and SHAKE256 over that key and the big-endian
+    /// counter is truncated to 12 bytes, so nonces reveal nothing about the counter or each
+    /// other and nothing needs to be transmitted. Distinct counters collide only with the
+    /// probability of a 96-bit hash collision.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, encapsulate};
+    /// let (pk, _sk) = keypair();
+    /// let (_, ss) = encapsulate(&pk);
+    /// assert_ne!(ss.nonce_for(0), ss.nonce_for(1));
+    /// ```
+    pub fn nonce_for(&self, counter: u64) -> [u8; 12] {
+        let mut nonce_key = Zeroizing::new([0u8; 32]);
+        self.expand(b"ruption-nonce-key", &mut nonce_key[..]);
+        let mut xof = Shake256::default();
+        xof.update(b"ruption-nonce-v2");
+        xof.update(&nonce_key[..]);
+        xof.update(&counter.to_be_bytes());
+        let mut nonce = [0u8; 12];
+        XofReader::read(&mut xof.finalize_xof(), &mut nonce);
+        nonce
+    }
+
+    /// Compares two secrets in constant time.
+    ///
+    /// Every byte is compared wherever the first difference lies; `==` on `SharedSecret` uses
+    /// this too, so prefer either over comparing `as_bytes()` slices.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, encapsulate, decapsulate};
+    /// let (pk, sk) = keypair();
+    /// let (ct, ss) = encapsulate(&pk);
+    /// assert!(ss.ct_eq(&decapsulate(&ct, &sk)));
+    /// ```
+    pub fn ct_eq(&self, other: &SharedSecret) -> bool {
+        ct_eq(&self.0, &other.0)
+    }
+
+    /// Computes a key-confirmation proof over `challenge`.
+    ///
+    /// A keyed SHA3-256 of the challenge; it reveals nothing about the secret. Both holders of
+    /// the same secret compute the same proof, so include the sender's role in the challenge
+    /// if both sides send one, or a proof could simply be reflected back.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, encapsulate, decapsulate, verify_agreement};
+    /// let (pk, sk) = keypair();
+    /// let (ct, ss) = encapsulate(&pk);
+    /// let proof = ss.agreement_proof(b"fresh challenge");
+    /// assert!(verify_agreement(&decapsulate(&ct, &sk), b"fresh challenge", &proof));
+    /// ```
+    pub fn agreement_proof(&self, challenge: &[u8]) -> [u8; 32] {
+        let mut hasher = Sha3_256::new();
+        Update::update(&mut hasher, b"ruption-agreement-proof-v1");
+        Update::update(&mut hasher, &(self.0.len() as u64).to_be_bytes());
+        Update::update(&mut hasher, &self.0);
+        Update::update(&mut hasher, challenge);
+        hasher.finalize().into()
+    }
+
+    /// Returns an unbounded keystream: SHAKE256 over the secret, read on demand.
+    ///
+    /// The stream is the concatenation of the keys `derive_keys` would return, so reading
+    /// `32 * n` bytes gives exactly `derive_keys(self, n)`. Equal secrets give equal streams.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, encapsulate, derive_keys};
+    /// use std::io::Read;
+    /// let (pk, _sk) = keypair();
+    /// let (_, ss) = encapsulate(&pk);
+    /// let mut key = [0u8; 32];
+    /// ss.key_stream().read_exact(&mut key).unwrap();
+    /// assert_eq!(key.to_vec(), derive_keys(&ss, 1)[0]);
+    /// ```
+    pub fn key_stream(&self) -> KeyStream {
+        let mut xof = Shake256::default();
+        xof.update(&self.0);
+        KeyStream(xof.finalize_xof())
+    }
+
+    /// Fills `out` from SHAKE256 over the secret followed by `context`.
+    fn expand(&self, context: &[u8], out: &mut [u8]) {
+        let mut xof = Shake256::default();
+        xof.update(&self.0);
+        xof.update(context);
+        XofReader::read(&mut xof.finalize_xof(), out);
+    }
+}
+
+/// Keystream derived from a `SharedSecret`, from `SharedSecret::key_stream`.
+///
+/// With `std` it implements `Read`, which never fails and always fills the whole buffer.
+pub struct KeyStream(sha3::Shake256Reader);
+
+impl KeyStream {
+    /// Fills `out` with the next bytes of the stream.
+    pub fn fill(&mut self, out: &mut [u8]) {
+        XofReader::read(&mut self.0, out);
+    }
+}
+
+#[cfg(feature = "std")]
+impl Read for KeyStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.fill(buf);
+        Ok(buf.len())
+    }
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Buffers wiped on this thread, by address, with whether every byte read back as zero.
+    static WIPES: std::cell::RefCell<Vec<(usize, bool)>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Zeroizes secret bytes, then unlocks them if the `mlock` feature locked them.
+///
+/// Never panics or allocates, so it is safe to run while unwinding. Wiping comes first so the
+/// pages cannot be swapped out while they still hold the secret.
+fn wipe(bytes: &mut Vec<u8>) {
+    // Zeroizing a Vec also clears it, so note the region to unlock first.
+    let (ptr, len, capacity) = (bytes.as_ptr(), bytes.len(), bytes.capacity());
+    bytes.zeroize();
+    memlock::unlock(ptr, len);
+    record_wipe(ptr, capacity);
+}
+
+/// In tests, reads back a just-zeroized allocation before it is freed and records whether
+/// it is all zero, so tests can check the memory a drop left behind; does nothing otherwise.
+fn record_wipe(ptr: *const u8, capacity: usize) {
+    #[cfg(test)]
+    {
+        // SAFETY: the caller's Vec still owns its allocation of `capacity` bytes at `ptr`, and
+        // `zeroize` has just written every one of them.
+        let zeroed = unsafe { core::slice::from_raw_parts(ptr, capacity) }.iter().all(|&b| b == 0);
+        WIPES.with(|wipes| wipes.borrow_mut().push((ptr as usize, zeroed)));
+    }
+    #[cfg(not(test))]
+    let _ = (ptr, capacity);
+}
+
+impl Drop for SecretKey {
+    fn drop(&mut self) {
+        wipe(&mut self._lattice_secret);
+        wipe(&mut self._code_secret);
+    }
+}
+
+impl PartialEq for SharedSecret {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other)
+    }
+}
+
+impl Eq for SharedSecret {}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        wipe(&mut self.0);
+    }
 }
 
 /// A generator for high-quality randomness approximating true entropy.
@@ -55,6 +756,10 @@ impl SharedSecret {
 /// Combines OS-provided entropy, timing jitter, and a quantum-inspired simulation.
 /// Suitable for cryptographic key generation in a software-only environment.
 ///
+/// Draws condense the entropy pool whenever it passes a fixed cap, so its size stays bounded
+/// however many draws are made. The pool is zeroized on drop; copies left behind when it
+/// outgrew an earlier allocation are not.
+///
 /// # Examples
 /// ```
 /// use ruption_quantum_encrypt::TrueRandom;
@@ -62,63 +767,390 @@ impl SharedSecret {
 /// let random_bytes = trng.generate(32);
 /// assert_eq!(random_bytes.len(), 32);
 /// ```
+#[cfg(feature = "std")]
 pub struct TrueRandom {
-    entropy_pool: Vec<u8>,        // Pool of collected entropy
+    entropy_pool: Vec<u8>,                    // Pool of collected entropy
+    clock: Box<dyn Clock + Send>,             // Time source for jitter and simulated entropy
+    jitter_rounds: usize,                     // Jitter samples collected per draw
+    sources: Vec<EntropySource>,              // Optional sources mixed in on each draw
+    reseed_interval: Option<u64>,             // Draws between automatic reseeds, if any
+    draws: u64,                               // Draws since the last automatic reseed
+    health: Option<health::ContinuousHealth>, // Continuous tests on jitter samples, if enabled
+    #[cfg(feature = "fault-injection")]
+    fail_next: bool,                          // Injected failure for the next generate call
+}
+
+/// A source of time for the timing-based entropy paths of `TrueRandom`.
+///
+/// `SystemClock` is used by default; tests can inject a deterministic clock
+/// through `TrueRandom::with_clock`.
+#[cfg(feature = "std")]
+pub trait Clock {
+    /// Returns the current instant.
+    fn now(&self) -> Instant;
+
+    /// Sleeps (or pretends to sleep) for the given duration.
+    fn sleep(&self, d: Duration);
+}
+
+/// The real system clock, backed by `Instant::now` and `thread::sleep`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, d: Duration) {
+        thread::sleep(d);
+    }
 }
 
+#[cfg(feature = "std")]
 impl TrueRandom {
     /// Initializes a new randomness generator with system entropy.
     ///
     /// Seeds the entropy pool with 64 bytes from the OS's secure random source.
-    /// Panics if entropy retrieval fails (rare on modern systems).
+    /// Panics if entropy retrieval fails (rare on modern systems); see `try_new`.
     pub fn new() -> Self {
-        let mut initial_entropy = vec![0u8; 64];
-        getrandom(&mut initial_entropy).expect("Failed to get system entropy");
-        TrueRandom {
-            entropy_pool: initial_entropy,
+        Self::with_clock(SystemClock)
+    }
+
+    /// Initializes a new generator, reporting a failure of the OS entropy source.
+    ///
+    /// # Returns
+    /// The seeded `TrueRandom`, or `QuantumError::EntropyUnavailable`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::try_new().unwrap();
+    /// assert_eq!(trng.try_generate(16).unwrap().len(), 16);
+    /// ```
+    pub fn try_new() -> Result<Self, QuantumError> {
+        Self::try_with_boxed_clock(Box::new(SystemClock))
+    }
+
+    /// Initializes a generator that reads time from `clock` instead of the system clock.
+    ///
+    /// The pool is still seeded from the OS; only jitter and simulated entropy use the clock.
+    /// Panics if OS entropy retrieval fails.
+    pub fn with_clock<C: Clock + Send + 'static>(clock: C) -> Self {
+        Self::try_with_boxed_clock(Box::new(clock)).expect("Failed to get system entropy")
+    }
+
+    /// Returns a `TrueRandomBuilder` for configuring a generator before first use.
+    pub fn builder() -> TrueRandomBuilder {
+        TrueRandomBuilder::new()
+    }
+
+    /// Creates a generator with default settings, seeded from the OS.
+    fn try_with_boxed_clock(clock: Box<dyn Clock + Send>) -> Result<Self, QuantumError> {
+        let mut trng = TrueRandom {
+            entropy_pool: Vec::new(),
+            clock,
+            jitter_rounds: JITTER_ROUNDS,
+            sources: EntropySource::DEFAULT.to_vec(),
+            reseed_interval: None,
+            draws: 0,
+            health: None,
+            #[cfg(feature = "fault-injection")]
+            fail_next: false,
+        };
+        trng.seed_from_os()?;
+        Ok(trng)
+    }
+
+    /// Turns the SP 800-90B continuous health tests on or off for this generator.
+    ///
+    /// When on, every `try_generate` runs the repetition count and adaptive proportion tests
+    /// over the raw jitter samples it collects, and returns `QuantumError::HealthFailure`
+    /// instead of output if either trips. A failure is permanent for this instance.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::new().with_continuous_health_check(true);
+    /// assert_eq!(trng.try_generate(32).unwrap().len(), 32);
+    /// ```
+    pub fn with_continuous_health_check(mut self, enabled: bool) -> Self {
+        self.health = enabled.then(health::ContinuousHealth::default);
+        self
+    }
+
+    /// Appends 64 bytes from the OS's secure random source to the pool.
+    fn seed_from_os(&mut self) -> Result<(), QuantumError> {
+        let mut os_entropy = [0u8; 64];
+        getrandom(&mut os_entropy).map_err(|_| QuantumError::EntropyUnavailable)?;
+        self.entropy_pool.extend_from_slice(&os_entropy);
+        os_entropy.zeroize();
+        Ok(())
+    }
+
+    /// Mixes `bytes` bytes read from `reader` into the pool.
+    ///
+    /// For entropy exposed as a file or device, such as `/dev/hwrng`. The bytes add to the
+    /// existing pool, so a weak source cannot make the output weaker than before.
+    ///
+    /// # Arguments
+    /// * `reader` - The entropy source.
+    /// * `bytes` - How many bytes to read from it.
+    ///
+    /// # Returns
+    /// `Ok(())`, or the read error; on error the pool is left unchanged.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::new();
+    /// trng.seed_from_reader(&mut &[7u8; 32][..], 32).unwrap();
+    /// assert!(trng.seed_from_reader(&mut &[7u8; 4][..], 32).is_err());
+    /// ```
+    pub fn seed_from_reader<R: Read>(&mut self, reader: &mut R, bytes: usize) -> std::io::Result<()> {
+        let mut external = vec![0u8; bytes];
+        let result = reader.read_exact(&mut external);
+        if result.is_ok() {
+            self.entropy_pool.extend_from_slice(&external);
         }
+        external.zeroize();
+        result
+    }
+
+    /// Condenses the pool to its SHA3-256 digest and mixes in 64 fresh bytes of OS entropy.
+    ///
+    /// For long-lived generators: call it periodically, or set
+    /// `TrueRandomBuilder::reseed_interval`, to refresh the state from the OS. It also caps the
+    /// pool at 96 bytes however many draws came before. If the OS source fails, the condensed
+    /// pool is kept and `QuantumError::EntropyUnavailable` returned.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::new();
+    /// trng.reseed().unwrap();
+    /// assert_eq!(trng.generate(32).len(), 32);
+    /// ```
+    pub fn reseed(&mut self) -> Result<(), QuantumError> {
+        self.condense_pool(&[]);
+        self.seed_from_os()
+    }
+
+    /// Condenses the pool together with application-supplied entropy, such as event timings.
+    ///
+    /// The pool becomes the SHA3-256 digest of the old pool and `extra`, so `extra` can only
+    /// add to the state: even a constant or attacker-known value leaves it no weaker.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::new();
+    /// trng.reseed_from(b"request id 1234, arrived at 17:02:11.041");
+    /// assert_eq!(trng.generate(32).len(), 32);
+    /// ```
+    pub fn reseed_from(&mut self, extra: &[u8]) {
+        self.condense_pool(extra);
+    }
+
+    /// Replaces the pool with the SHA3-256 digest of the pool followed by `extra`.
+    fn condense_pool(&mut self, extra: &[u8]) {
+        let mut hasher = Sha3_256::new();
+        Update::update(&mut hasher, &self.entropy_pool);
+        Update::update(&mut hasher, extra);
+        self.entropy_pool.zeroize();
+        self.entropy_pool.clear();
+        self.entropy_pool.extend_from_slice(&hasher.finalize());
     }
 
     /// Collects timing jitter from thread scheduling to enhance entropy.
-    fn collect_jitter(&mut self) {
-        let mut jitter = Vec::new();
-        for _ in 0..10 {
-            let start = Instant::now();
-            thread::sleep(Duration::from_nanos(1));
-            let elapsed = start.elapsed().as_nanos() as u8;
-            jitter.push(elapsed);
+    ///
+    /// With health checks on, the samples are tested first and dropped if a test trips.
+    fn collect_jitter(&mut self) -> Result<(), QuantumError> {
+        let jitter = self.jitter_samples(self.jitter_rounds);
+        if let Some(health) = &mut self.health {
+            if !health.check(&jitter) {
+                return Err(QuantumError::HealthFailure);
+            }
         }
         self.entropy_pool.extend(jitter);
+        Ok(())
+    }
+
+    /// Times `count` short sleeps, keeping the low byte of each duration in nanoseconds.
+    fn jitter_samples(&self, count: usize) -> Vec<u8> {
+        let mut samples = Vec::with_capacity(count);
+        for _ in 0..count {
+            let start = self.clock.now();
+            self.clock.sleep(Duration::from_nanos(1));
+            samples.push(self.clock.now().saturating_duration_since(start).as_nanos() as u8);
+        }
+        samples
+    }
+
+    /// Runs SP 800-90B-style startup tests on the jitter source and on a block of output.
+    ///
+    /// Collects 1024 raw jitter samples (if jitter is among the sources) and 1024 output
+    /// bytes, and runs the repetition count and adaptive proportion tests over each. Raw
+    /// samples use the cutoffs of `with_continuous_health_check`; output uses much tighter
+    /// ones, since conditioned output should look uniform, so only pathological output such
+    /// as a long run of one byte fails. The samples passed are mixed into the pool.
+    ///
+    /// Not run by `new`, since the jitter samples take about a thousand sleeps; call it once
+    /// after construction, and discard the generator if it fails.
+    ///
+    /// # Returns
+    /// `Ok(())`, `QuantumError::HealthFailure` if either test trips, or the error of
+    /// `try_generate`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::new();
+    /// trng.health_check().unwrap();
+    /// ```
+    pub fn health_check(&mut self) -> Result<(), QuantumError> {
+        const STARTUP_SAMPLES: usize = 1024;
+        if self.sources.contains(&EntropySource::Jitter) {
+            let samples = self.jitter_samples(STARTUP_SAMPLES);
+            if !health::ContinuousHealth::default().check(&samples) {
+                return Err(QuantumError::HealthFailure);
+            }
+            self.entropy_pool.extend(samples);
+        }
+        let mut output = self.try_generate(STARTUP_SAMPLES)?;
+        let healthy = health::ContinuousHealth::for_output().check(&output);
+        output.zeroize();
+        if !healthy {
+            return Err(QuantumError::HealthFailure);
+        }
+        Ok(())
     }
 
     /// Simulates a quantum-inspired entropy source using system timing.
     ///
     /// Approximates unpredictable behavior in software; not true quantum randomness.
-    fn quantum_sim_entropy(&mut self) -> Vec<u8> {
+    fn quantum_sim_entropy(&mut self) -> Result<Vec<u8>, QuantumError> {
+        if self.entropy_pool.is_empty() {
+            self.seed_from_os()?;
+        }
         let mut sim_entropy = Vec::new();
-        let now = Instant::now().elapsed().as_nanos();
+        let start = self.clock.now();
+        let now = self.clock.now().saturating_duration_since(start).as_nanos();
         let mut state = now as u64;
 
         for _ in 0..16 {
-            state ^= state.wrapping_add(self.entropy_pool[state as usize % self.entropy_pool.len()] as u64);
+            // Reduce in u64 before narrowing so 32-bit targets index with the full state.
+            let index = (state % self.entropy_pool.len() as u64) as usize;
+            state ^= state.wrapping_add(self.entropy_pool[index] as u64);
             sim_entropy.push((state & 0xFF) as u8);
         }
-        sim_entropy
+        Ok(sim_entropy)
     }
 
     /// Generates random bytes of the specified length.
     ///
     /// Mixes OS entropy, jitter, and simulated quantum entropy with SHA-3 for uniformity.
+    /// With the `x86-hwrng` feature, RDSEED output is mixed in too when the CPU supports it.
     ///
     /// # Arguments
     /// * `len` - The number of bytes to generate.
     ///
     /// # Returns
     /// A `Vec<u8>` of random bytes.
+    ///
+    /// # Panics
+    /// If a continuous health test trips or a failure was injected with
+    /// `fail_next_generate`; see `try_generate`.
     pub fn generate(&mut self, len: usize) -> Vec<u8> {
-        self.collect_jitter();
-        let sim_entropy = self.quantum_sim_entropy();
-        self.entropy_pool.extend(sim_entropy);
+        self.try_generate(len).expect("random generation failed")
+    }
+
+    /// Generates random bytes, reporting entropy failures instead of panicking.
+    ///
+    /// # Returns
+    /// The random bytes, `QuantumError::HealthFailure` if a continuous health test tripped
+    /// (see `with_continuous_health_check`), or `QuantumError::EntropyUnavailable`.
+    pub fn try_generate(&mut self, len: usize) -> Result<Vec<u8>, QuantumError> {
+        #[cfg(feature = "fault-injection")]
+        if std::mem::take(&mut self.fail_next) {
+            return Err(QuantumError::EntropyUnavailable);
+        }
+        let output = self.mix_output(len)?;
+        if let Some(interval) = self.reseed_interval {
+            self.draws += 1;
+            if self.draws >= interval {
+                self.draws = 0;
+                self.reseed()?;
+            }
+        }
+        Ok(output)
+    }
+
+    /// Generates random bytes from the pool alone, skipping timing jitter and simulated entropy.
+    ///
+    /// Each call squeezes the output and a replacement pool from one SHAKE256 pass over the
+    /// current pool, so successive outputs are unrelated and earlier ones cannot be recovered
+    /// from the new state. No fresh entropy is added: the output is only as unpredictable as
+    /// the pool already is, which after OS seeding is enough for keys and keystreams. Calls
+    /// are not counted toward `TrueRandomBuilder::reseed_interval`, so call `reseed` from
+    /// time to time when drawing heavily. Use `generate` when every draw should carry new
+    /// jitter; it is far slower for many small draws because each one sleeps.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::new();
+    /// let keystream = trng.generate_fast(1 << 20);
+    /// assert_eq!(keystream.len(), 1 << 20);
+    /// assert_ne!(trng.generate_fast(32), trng.generate_fast(32));
+    /// ```
+    pub fn generate_fast(&mut self, len: usize) -> Vec<u8> {
+        let mut xof = Shake256::default();
+        xof.update(b"ruption-fast-v1");
+        xof.update(&self.entropy_pool);
+        let mut reader = xof.finalize_xof();
+        let mut next_pool = [0u8; 32];
+        XofReader::read(&mut reader, &mut next_pool);
+        self.entropy_pool.zeroize();
+        self.entropy_pool.clear();
+        self.entropy_pool.extend_from_slice(&next_pool);
+        next_pool.zeroize();
+
+        let mut output = vec![0u8; len];
+        XofReader::read(&mut reader, &mut output);
+        output
+    }
+
+    /// Makes the next `generate` or `try_generate` call fail, for testing error handling.
+    ///
+    /// `try_generate` returns `QuantumError::EntropyUnavailable`; `generate` panics.
+    /// Only available with the `fault-injection` feature; never enable it in production.
+    #[cfg(feature = "fault-injection")]
+    pub fn fail_next_generate(&mut self) {
+        self.fail_next = true;
+    }
+
+    /// Refreshes the pool and squeezes `len` output bytes from it.
+    fn mix_output(&mut self, len: usize) -> Result<Vec<u8>, QuantumError> {
+        if self.sources.contains(&EntropySource::Jitter) {
+            self.collect_jitter()?;
+        }
+        if self.sources.contains(&EntropySource::QuantumSim) {
+            let sim_entropy = self.quantum_sim_entropy()?;
+            self.entropy_pool.extend(sim_entropy);
+        }
+        #[cfg(all(feature = "x86-hwrng", any(target_arch = "x86", target_arch = "x86_64")))]
+        if self.sources.contains(&EntropySource::HardwareRng) {
+            if let Some(hw_entropy) = hwrng::rdseed_bytes(16) {
+                self.entropy_pool.extend(hw_entropy);
+            }
+        }
+
+        if self.entropy_pool.len() > POOL_CAP {
+            self.condense_pool(b"ruption-pool-fold-v1");
+        }
 
         let mut hasher = Sha3_256::new();
         Update::update(&mut hasher, &self.entropy_pool);
@@ -129,14 +1161,108 @@ impl TrueRandom {
             xof.update(&mixed);
             let mut reader = xof.finalize_xof();
             let mut output = vec![0u8; len];
-            reader.read_exact(&mut output).unwrap();
-            output
+            XofReader::read(&mut reader, &mut output);
+            Ok(output)
         } else {
-            mixed[..len].to_vec()
+            Ok(mixed[..len].to_vec())
         }
     }
-}
 
+    /// Combines output from several independent generators into `len` bytes.
+    ///
+    /// Draws 32 bytes from each instance and hashes them together with SHAKE256, so the
+    /// result is unpredictable as long as any one instance is.
+    ///
+    /// # Panics
+    /// If `instances` is empty.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut sources = [TrueRandom::new(), TrueRandom::new()];
+    /// assert_eq!(TrueRandom::combine(&mut sources, 64).len(), 64);
+    /// ```
+    pub fn combine(instances: &mut [TrueRandom], len: usize) -> Vec<u8> {
+        assert!(!instances.is_empty(), "combine needs at least one generator");
+        let mut xof = Shake256::default();
+        xof.update(b"ruption-combine-v1");
+        xof.update(&(instances.len() as u64).to_be_bytes());
+        for trng in instances.iter_mut() {
+            let mut draw = trng.generate(32);
+            xof.update(&draw);
+            draw.zeroize();
+        }
+        let mut output = vec![0u8; len];
+        XofReader::read(&mut xof.finalize_xof(), &mut output);
+        output
+    }
+
+    /// Generates `byte_len` random bytes as a lowercase hex string.
+    ///
+    /// # Returns
+    /// A `String` of `2 * byte_len` hex digits.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::TrueRandom;
+    /// let mut trng = TrueRandom::new();
+    /// assert_eq!(trng.generate_hex(16).len(), 32);
+    /// ```
+    pub fn generate_hex(&mut self, byte_len: usize) -> String {
+        to_hex(&self.generate(byte_len))
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for TrueRandom {
+    fn drop(&mut self) {
+        // The pool is not secret key material and never locked, so it is only zeroized.
+        let (ptr, capacity) = (self.entropy_pool.as_ptr(), self.entropy_pool.capacity());
+        self.entropy_pool.zeroize();
+        record_wipe(ptr, capacity);
+    }
+}
+
+/// Routes every `RngCore` method through `try_generate`, so output is mixed exactly as
+/// `generate` mixes it; lengths over 32 bytes are squeezed from SHAKE256.
+///
+/// # Examples
+/// ```
+/// use rand_core::RngCore;
+/// use ruption_quantum_encrypt::TrueRandom;
+/// let mut trng = TrueRandom::new();
+/// let mut buf = [0u8; 64];
+/// trng.fill_bytes(&mut buf);
+/// assert_ne!(trng.next_u64(), trng.next_u64());
+/// ```
+#[cfg(feature = "std")]
+impl RngCore for TrueRandom {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    /// # Panics
+    /// Where `generate` would; see `try_fill_bytes`.
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).expect("random generation failed")
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        let mut bytes = self.try_generate(dest.len()).map_err(rand_core::Error::new)?;
+        dest.copy_from_slice(&bytes);
+        bytes.zeroize();
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl CryptoRng for TrueRandom {}
+
+#[cfg(feature = "std")]
 impl Default for TrueRandom {
     /// Provides a default instance of `TrueRandom`.
     ///
@@ -148,36 +1274,115 @@ impl Default for TrueRandom {
 
 /// Generates a keypair for quantum-secure encryption.
 ///
-/// Uses `TrueRandom` to produce unpredictable keys.
+/// Draws from the shared `global_rng` to produce unpredictable keys.
 /// This is a simplified version; real-world use requires proper cryptographic math.
 ///
 /// # Returns
 /// A tuple `(PublicKey, SecretKey)` for use in encryption/decryption.
 ///
+/// Requires `std`; without it, use `keypair_with_rng`.
+///
 /// # Examples
 /// ```
 /// use ruption_quantum_encrypt::keypair;
 /// let (pk, sk) = keypair();
 /// ```
+#[cfg(feature = "std")]
 pub fn keypair() -> (PublicKey, SecretKey) {
-    let mut trng = TrueRandom::new();
+    keypair_with_params(&Params::toy())
+}
 
-    let lattice_secret = trng.generate(LATTICE_DIM);
-    let lattice_matrix = vec![trng.generate(LATTICE_DIM); LATTICE_DIM];
+/// Generates a keypair drawing all randomness from `rng`.
+///
+/// For reproducible test vectors, or to use an already vetted DRBG: the same RNG state
+/// always gives the same keypair. `rng` must be cryptographically secure for real keys.
+///
+/// # Arguments
+/// * `rng` - The random number generator to draw from.
+///
+/// # Returns
+/// A tuple `(PublicKey, SecretKey)`.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair_with_rng, encapsulate_with_rng, decapsulate, TrueRandom};
+/// let mut rng = TrueRandom::new();
+/// let (pk, sk) = keypair_with_rng(&mut rng);
+/// let (ct, ss) = encapsulate_with_rng(&pk, &mut rng);
+/// assert!(decapsulate(&ct, &sk) == ss);
+/// ```
+pub fn keypair_with_rng<R: RngCore>(rng: &mut R) -> (PublicKey, SecretKey) {
+    keypair_from(&Params::toy(), rng_source(rng))
+}
 
-    let code_secret = trng.generate(CODE_LENGTH / 8);
-    let code_generator = trng.generate(CODE_LENGTH);
+/// Adapts `rng` to the `random(len)` closures taken by `keypair_from` and `encapsulate_from`.
+fn rng_source<R: RngCore>(rng: &mut R) -> impl FnMut(usize) -> Vec<u8> + '_ {
+    move |len| {
+        let mut bytes = vec![0u8; len];
+        rng.fill_bytes(&mut bytes);
+        bytes
+    }
+}
 
-    (
-        PublicKey {
-            _lattice_matrix: lattice_matrix,
-            _code_generator: code_generator,
-        },
-        SecretKey {
-            _lattice_secret: lattice_secret,
-            _code_secret: code_secret,
-        },
-    )
+/// Generates a keypair for the parameter set `params`.
+///
+/// The keys record their parameters (see `PublicKey::params`): `encapsulate` sizes the
+/// ciphertext to match, and `try_decapsulate` refuses ciphertexts made for other parameters.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair_with_params, encapsulate, try_decapsulate, Params};
+/// let params = Params::new(64, 128).unwrap();
+/// let (pk, sk) = keypair_with_params(&params);
+/// let (ct, ss) = encapsulate(&pk);
+/// assert_eq!(ct.params(), params);
+/// assert!(try_decapsulate(&ct, &sk).unwrap() == ss);
+/// ```
+#[cfg(feature = "std")]
+pub fn keypair_with_params(params: &Params) -> (PublicKey, SecretKey) {
+    keypair_from(params, random_bytes)
+}
+
+/// Fills `dest` from the default randomness source behind `keypair` and `encapsulate`.
+///
+/// This is the process-wide `TrueRandom` of `global_rng`. A `no_std` build has no implicit
+/// source, so every function that draws from it needs `std`; use the `_with_rng` functions there.
+///
+/// # Panics
+/// If the randomness source fails.
+#[cfg(feature = "std")]
+pub(crate) fn fill_random(dest: &mut [u8]) {
+    global_rng().fill(dest);
+}
+
+/// Returns `len` bytes from `fill_random`.
+#[cfg(feature = "std")]
+pub(crate) fn random_bytes(len: usize) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    fill_random(&mut bytes);
+    bytes
+}
+
+/// Generates a keypair for `params`, drawing all randomness from `random(len)`.
+pub(crate) fn keypair_from(params: &Params, mut random: impl FnMut(usize) -> Vec<u8>) -> (PublicKey, SecretKey) {
+    let lattice_dim = params.lattice_dim();
+    let code_length = params.code_length();
+
+    let mut cbd_bits = random(lattice_dim.div_ceil(2));
+    let lattice_secret = sample_cbd(&cbd_bits, lattice_dim);
+    cbd_bits.zeroize();
+    let code_secret = random(code_length / 8);
+
+    memlock::lock(&lattice_secret);
+    memlock::lock(&code_secret);
+    let mut sk = SecretKey {
+        _lattice_secret: lattice_secret,
+        _code_secret: code_secret,
+        public_digest: [0u8; 32],
+    };
+    let pk = PublicKey::from_secret(&sk);
+    sk.public_digest = public_digest(&pk);
+    (pk, sk)
 }
 
 /// Encapsulates a shared secret using the public key.
@@ -191,22 +1396,80 @@ pub fn keypair() -> (PublicKey, SecretKey) {
 /// # Returns
 /// A tuple `(Ciphertext, SharedSecret)` with the encrypted data and secret.
 ///
+/// Requires `std`; without it, use `encapsulate_with_rng`.
+///
 /// # Examples
 /// ```
 /// use ruption_quantum_encrypt::{keypair, encapsulate};
 /// let (pk, _sk) = keypair();
 /// let (ct, ss) = encapsulate(&pk);
 /// ```
-pub fn encapsulate(_pk: &PublicKey) -> (Ciphertext, SharedSecret) {
-    let mut trng = TrueRandom::new();
+#[cfg(feature = "std")]
+pub fn encapsulate(pk: &PublicKey) -> (Ciphertext, SharedSecret) {
+    encapsulate_from(pk, random_bytes)
+}
 
-    let lattice_cipher = trng.generate(LATTICE_DIM);
-    let code_cipher = trng.generate(CODE_LENGTH);
+/// Encapsulates to `pk` after checking that it was generated for `params`.
+///
+/// Pins the expected parameter set, so a key from another level is refused rather than used.
+///
+/// # Returns
+/// The ciphertext and secret, or `QuantumError::InvalidParams` if `pk` is for other parameters.
+#[cfg(feature = "std")]
+pub fn encapsulate_with_params(pk: &PublicKey, params: &Params) -> Result<(Ciphertext, SharedSecret), QuantumError> {
+    if pk.params() != *params {
+        return Err(QuantumError::InvalidParams);
+    }
+    Ok(encapsulate(pk))
+}
 
-    let mut hasher = Sha3_256::new();
-    Update::update(&mut hasher, &lattice_cipher);
-    Update::update(&mut hasher, &code_cipher);
-    let shared_secret = SharedSecret(hasher.finalize().to_vec());
+/// Encapsulates to `pk`, drawing all randomness from `rng`.
+///
+/// The same RNG state always gives the same ciphertext and secret; see `keypair_with_rng`.
+///
+/// # Returns
+/// A tuple `(Ciphertext, SharedSecret)`.
+pub fn encapsulate_with_rng<R: RngCore>(pk: &PublicKey, rng: &mut R) -> (Ciphertext, SharedSecret) {
+    encapsulate_from(pk, rng_source(rng))
+}
+
+/// Encapsulates a fresh shared secret to each of several recipients.
+///
+/// Every encapsulation draws from the one process-wide generator, so a large batch costs no
+/// OS reseeds beyond the generator's usual schedule. Secrets are independent per recipient;
+/// to give a group one key, wrap it under each secret with `seal_group_key`.
+///
+/// # Arguments
+/// * `pks` - The recipients' public keys, owned or borrowed.
+///
+/// # Returns
+/// One `(Ciphertext, SharedSecret)` per key, in the order of `pks`.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate_batch, decapsulate};
+/// let (alice_pk, alice_sk) = keypair();
+/// let (bob_pk, _bob_sk) = keypair();
+/// let batch = encapsulate_batch(&[alice_pk, bob_pk]);
+/// assert_eq!(decapsulate(&batch[0].0, &alice_sk).as_bytes(), batch[0].1.as_bytes());
+/// ```
+#[cfg(feature = "std")]
+pub fn encapsulate_batch<K: core::borrow::Borrow<PublicKey>>(pks: &[K]) -> Vec<(Ciphertext, SharedSecret)> {
+    pks.iter().map(|pk| encapsulate_from(pk.borrow(), random_bytes)).collect()
+}
+
+/// Encapsulates to `pk`, drawing all randomness from `random(len)`.
+pub(crate) fn encapsulate_from(pk: &PublicKey, mut random: impl FnMut(usize) -> Vec<u8>) -> (Ciphertext, SharedSecret) {
+    let mut message = Zeroizing::new([0u8; lwe::MESSAGE_LEN]);
+    let mut drawn = random(lwe::MESSAGE_LEN);
+    message.copy_from_slice(&drawn);
+    drawn.zeroize();
+
+    // Component sizes follow the key, so keys from any `Params` encapsulate correctly.
+    let digest = public_digest(pk);
+    let lattice_cipher = lwe::encrypt(&pk._lattice_matrix, &message, &digest);
+    let code_cipher = random(pk._code_generator.len());
+    let shared_secret = shared_secret_from(&lattice_cipher, &code_cipher, &digest, &message[..]);
 
     (
         Ciphertext {
@@ -219,8 +1482,14 @@ pub fn encapsulate(_pk: &PublicKey) -> (Ciphertext, SharedSecret) {
 
 /// Decapsulates the ciphertext to retrieve the shared secret.
 ///
-/// Uses the secret key to recover the shared secret.
-/// Simplified for demonstration; real decryption would use the secret key.
+/// Decrypts the toy LWE component with `sk` to recover the message `encapsulate` drew, then
+/// hashes it with the ciphertext and the public key digest. Without the secret key the
+/// message, and so the secret, cannot be computed from the public key and ciphertext. There
+/// is no re-encryption check, so a tampered ciphertext or another key pair's secret key
+/// gives an unrelated secret rather than an error. The code-based component is still a
+/// random placeholder: it is hashed in but contributes no secrecy.
+/// The ciphertext's parameter set is not checked; use `try_decapsulate` to refuse ciphertexts
+/// made for another one.
 ///
 /// # Arguments
 /// * `ct` - The `Ciphertext` to decapsulate.
@@ -237,11 +1506,245 @@ pub fn encapsulate(_pk: &PublicKey) -> (Ciphertext, SharedSecret) {
 /// let ss2 = decapsulate(&ct, &sk);
 /// assert_eq!(ss1.as_bytes(), ss2.as_bytes());
 /// ```
-pub fn decapsulate(ct: &Ciphertext, _sk: &SecretKey) -> SharedSecret {
+pub fn decapsulate(ct: &Ciphertext, sk: &SecretKey) -> SharedSecret {
+    let message = lwe::decrypt(&ct.lattice_cipher, &sk._lattice_secret);
+    shared_secret_from(&ct.lattice_cipher, &ct.code_cipher, &sk.public_digest, &message[..])
+}
+
+/// Hashes the ciphertext, the public key digest and the encrypted message into a secret.
+///
+/// Every field is length-framed after a domain label. Only the message is secret; the
+/// ciphertext and digest bind the result to this exchange and key pair.
+fn shared_secret_from(lattice_cipher: &[u8], code_cipher: &[u8], digest: &[u8; 32], message: &[u8]) -> SharedSecret {
+    let mut hasher = shared_secret_hasher();
+    aead::absorb_framed(&mut hasher, lattice_cipher);
+    aead::absorb_framed(&mut hasher, code_cipher);
+    finish_shared_secret(hasher, digest, message)
+}
+
+/// Starts the hash `shared_secret_from` computes.
+fn shared_secret_hasher() -> Sha3_256 {
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, b"ruption-kem-shared-secret-v2");
+    hasher
+}
+
+/// Completes the hash `shared_secret_from` computes, once the ciphertext has been absorbed.
+fn finish_shared_secret(mut hasher: Sha3_256, digest: &[u8; 32], message: &[u8]) -> SharedSecret {
+    aead::absorb_framed(&mut hasher, digest);
+    aead::absorb_framed(&mut hasher, message);
+    SharedSecret::new(hasher.finalize().to_vec())
+}
+
+#[cfg(feature = "fault-injection")]
+thread_local! {
+    static FAIL_NEXT_DECAPSULATE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Decapsulates after checking that the ciphertext fits the secret key.
+///
+/// # Returns
+/// The `SharedSecret`, `QuantumError::InvalidLength` if the ciphertext was made for another
+/// parameter set than `sk`, or `QuantumError::DecapsulationFailed` if decapsulation failed.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, try_decapsulate};
+/// let (pk, sk) = keypair();
+/// let (ct, ss) = encapsulate(&pk);
+/// assert_eq!(try_decapsulate(&ct, &sk).unwrap().as_bytes(), ss.as_bytes());
+/// ```
+pub fn try_decapsulate(ct: &Ciphertext, sk: &SecretKey) -> Result<SharedSecret, QuantumError> {
+    if ct.params() != sk.params() {
+        return Err(QuantumError::InvalidLength);
+    }
+    #[cfg(feature = "fault-injection")]
+    if FAIL_NEXT_DECAPSULATE.with(|fail| fail.replace(false)) {
+        return Err(QuantumError::DecapsulationFailed);
+    }
+    Ok(decapsulate(ct, sk))
+}
+
+/// Makes the next `try_decapsulate` on this thread return `QuantumError::DecapsulationFailed`.
+///
+/// For testing error handling. Only available with the `fault-injection` feature; never
+/// enable it in production.
+#[cfg(feature = "fault-injection")]
+pub fn fail_next_decapsulate() {
+    FAIL_NEXT_DECAPSULATE.with(|fail| fail.set(true));
+}
+
+/// Incremental decapsulation for ciphertexts that arrive in chunks.
+///
+/// Feed the bytes of `Ciphertext::to_bytes`, in any chunk sizes, without holding the whole
+/// `Ciphertext` in memory. The header and length prefixes are checked as they arrive.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, DecapsulationStream};
+/// let (pk, sk) = keypair();
+/// let (ct, ss) = encapsulate(&pk);
+/// let mut stream = DecapsulationStream::new(&sk);
+/// for chunk in ct.to_bytes().chunks(1000) {
+///     stream.update(chunk);
+/// }
+/// assert_eq!(stream.finalize().unwrap().as_bytes(), ss.as_bytes());
+/// ```
+pub struct DecapsulationStream<'a> {
+    sk: &'a SecretKey,               // Secret key, bound in when the stream is finalized
+    hasher: Sha3_256,                // Running shared-secret hash over the ciphertext bytes
+    decryptor: lwe::Decryptor,       // Recovers the message from the lattice component
+    field: StreamField,              // Part of the encoding being read
+    remaining: usize,                // Bytes of `field` still to come
+    pending: Vec<u8>,                // Header or length prefix bytes read so far
+    error: Option<QuantumError>,     // First malformation seen, reported by `finalize`
+}
+
+/// The parts of an encoded `Ciphertext`, in the order `DecapsulationStream` reads them.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StreamField {
+    Header,
+    LatticeLength,
+    Lattice,
+    CodeLength,
+    Code,
+    Done,
+}
+
+impl<'a> DecapsulationStream<'a> {
+    /// Starts a streaming decapsulation with the recipient's secret key.
+    pub fn new(sk: &'a SecretKey) -> Self {
+        DecapsulationStream {
+            sk,
+            hasher: shared_secret_hasher(),
+            decryptor: lwe::Decryptor::new(&sk._lattice_secret),
+            field: StreamField::Header,
+            remaining: CIPHERTEXT_HEADER_LEN,
+            pending: Vec::with_capacity(CIPHERTEXT_HEADER_LEN),
+            error: None,
+        }
+    }
+
+    /// Processes the next chunk of encoded ciphertext bytes.
+    ///
+    /// Input after the first malformation is ignored; `finalize` reports the error.
+    pub fn update(&mut self, mut chunk: &[u8]) {
+        while !chunk.is_empty() && self.error.is_none() {
+            if self.field == StreamField::Done {
+                self.error = Some(QuantumError::Deserialize);
+                break;
+            }
+            let (bytes, rest) = chunk.split_at(chunk.len().min(self.remaining));
+            match self.field {
+                StreamField::Lattice => {
+                    self.decryptor.update(bytes);
+                    Update::update(&mut self.hasher, bytes);
+                }
+                StreamField::Code => Update::update(&mut self.hasher, bytes),
+                _ => self.pending.extend_from_slice(bytes),
+            }
+            self.remaining -= bytes.len();
+            chunk = rest;
+            if self.remaining == 0 {
+                self.next_field();
+            }
+        }
+    }
+
+    /// Checks the field just completed and moves on to the next one.
+    fn next_field(&mut self) {
+        let params = self.sk.params();
+        let pending = core::mem::take(&mut self.pending);
+        let (field, expected) = match self.field {
+            StreamField::Header => {
+                if pending[..4] != CIPHERTEXT_MAGIC[..] || pending[4] != CIPHERTEXT_VERSION {
+                    self.error = Some(QuantumError::Deserialize);
+                }
+                (StreamField::LatticeLength, 4)
+            }
+            StreamField::LatticeLength => (StreamField::Lattice, lwe::cipher_len(params.lattice_dim())),
+            StreamField::Lattice => (StreamField::CodeLength, 4),
+            StreamField::CodeLength => (StreamField::Code, params.code_length()),
+            StreamField::Code | StreamField::Done => (StreamField::Done, 0),
+        };
+        if matches!(field, StreamField::Lattice | StreamField::Code) {
+            // A component sized for other parameters cannot decapsulate under this key.
+            if codec::take_u32(&mut &pending[..]) != Ok(expected as u32) {
+                self.error = Some(QuantumError::InvalidLength);
+            }
+            aead::absorb_len(&mut self.hasher, expected);
+        }
+        self.field = field;
+        self.remaining = expected;
+    }
+
+    /// Completes decapsulation once the whole ciphertext has been fed in.
+    ///
+    /// # Returns
+    /// The `SharedSecret`, identical to what `decapsulate` yields for the same ciphertext,
+    /// `QuantumError::Deserialize` if the header is wrong or the input is truncated or has
+    /// trailing bytes, or `QuantumError::InvalidLength` if a component is sized for another
+    /// parameter set than the key's.
+    pub fn finalize(self) -> Result<SharedSecret, QuantumError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.field != StreamField::Done {
+            return Err(QuantumError::Deserialize);
+        }
+        let message = self.decryptor.finish();
+        Ok(finish_shared_secret(self.hasher, &self.sk.public_digest, &message[..]))
+    }
+}
+
+/// Folds a public randomness beacon into a shared secret.
+///
+/// The beacon is public, so it adds no secrecy; it binds the exchange to a beacon round.
+fn bind_beacon(shared_secret: SharedSecret, beacon: &[u8]) -> SharedSecret {
     let mut hasher = Sha3_256::new();
-    Update::update(&mut hasher, &ct.lattice_cipher);
-    Update::update(&mut hasher, &ct.code_cipher);
-    SharedSecret(hasher.finalize().to_vec())
+    Update::update(&mut hasher, b"ruption-beacon-v1");
+    Update::update(&mut hasher, &shared_secret.0);
+    Update::update(&mut hasher, &(beacon.len() as u64).to_be_bytes());
+    Update::update(&mut hasher, beacon);
+    SharedSecret::new(hasher.finalize().to_vec())
+}
+
+/// Encapsulates a shared secret bound to a public randomness beacon value.
+///
+/// Both sides must use the same beacon (e.g. a drand round output) to agree on the secret.
+///
+/// # Arguments
+/// * `pk` - The recipient’s `PublicKey`.
+/// * `beacon` - The public beacon value to bind into the shared secret.
+///
+/// # Returns
+/// A tuple `(Ciphertext, SharedSecret)` with the encrypted data and beacon-bound secret.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate_with_beacon, decapsulate_with_beacon};
+/// let (pk, sk) = keypair();
+/// let (ct, ss1) = encapsulate_with_beacon(&pk, b"round-1024");
+/// let ss2 = decapsulate_with_beacon(&ct, &sk, b"round-1024");
+/// assert_eq!(ss1.as_bytes(), ss2.as_bytes());
+/// ```
+#[cfg(feature = "std")]
+pub fn encapsulate_with_beacon(pk: &PublicKey, beacon: &[u8]) -> (Ciphertext, SharedSecret) {
+    let (ciphertext, shared_secret) = encapsulate(pk);
+    (ciphertext, bind_beacon(shared_secret, beacon))
+}
+
+/// Decapsulates a ciphertext produced by `encapsulate_with_beacon`.
+///
+/// # Arguments
+/// * `ct` - The `Ciphertext` to decapsulate.
+/// * `sk` - The `SecretKey` for decryption.
+/// * `beacon` - The same public beacon value used during encapsulation.
+///
+/// # Returns
+/// The beacon-bound `SharedSecret`.
+pub fn decapsulate_with_beacon(ct: &Ciphertext, sk: &SecretKey, beacon: &[u8]) -> SharedSecret {
+    bind_beacon(decapsulate(ct, sk), beacon)
 }
 
 /// Derives multiple keys from a shared secret.
@@ -265,33 +1768,754 @@ pub fn decapsulate(ct: &Ciphertext, _sk: &SecretKey) -> SharedSecret {
 /// assert_eq!(keys[0].len(), 32);
 /// ```
 pub fn derive_keys(shared_secret: &SharedSecret, num_keys: usize) -> Vec<Vec<u8>> {
-    let mut keys = Vec::new();
     let mut xof = Shake256::default();
     xof.update(&shared_secret.0);
-    let mut reader = xof.finalize_xof();
+    derive_keys_from_xof(xof, num_keys, 32)
+}
+
+/// Derives multiple keys for one purpose, separated from every other purpose by `context`.
+///
+/// The context is absorbed after the secret, so `"enc"` and `"mac"` give unrelated key
+/// streams and swapping two call sites can no longer silently swap their keys. The same
+/// secret and context always yield the same keys; the keys are the output of
+/// `SharedSecret::expand` with that context, and an empty context gives `derive_keys`.
+///
+/// # Arguments
+/// * `shared_secret` - The `SharedSecret` to derive keys from.
+/// * `context` - A label naming what the keys are for.
+/// * `num_keys` - Number of 32-byte keys to generate.
+///
+/// # Returns
+/// A `Vec<Vec<u8>>` of derived keys.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, derive_keys_with_context};
+/// let (pk, _sk) = keypair();
+/// let (_, ss) = encapsulate(&pk);
+/// let enc = derive_keys_with_context(&ss, b"enc", 1);
+/// let mac = derive_keys_with_context(&ss, b"mac", 1);
+/// assert_ne!(enc, mac);
+/// assert_eq!(enc, derive_keys_with_context(&ss, b"enc", 1));
+/// ```
+pub fn derive_keys_with_context(shared_secret: &SharedSecret, context: &[u8], num_keys: usize) -> Vec<Vec<u8>> {
+    let mut xof = Shake256::default();
+    xof.update(&shared_secret.0);
+    xof.update(context);
+    derive_keys_from_xof(xof, num_keys, 32)
+}
+
+/// Security level of the SHAKE function used by `derive_keys_with_security`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Security {
+    /// SHAKE128: faster, 128-bit security margin.
+    Bits128,
+    /// SHAKE256, as used by `derive_keys`.
+    Bits256,
+}
 
+/// Derives multiple keys with an explicit choice of SHAKE128 or SHAKE256.
+///
+/// `Security::Bits256` gives exactly the keys of `derive_keys`. `Security::Bits128` is faster
+/// but yields entirely different keys, so both sides must pick the same level.
+///
+/// # Arguments
+/// * `shared_secret` - The `SharedSecret` to derive keys from.
+/// * `security` - Which SHAKE function to expand the secret with.
+/// * `num_keys` - Number of 32-byte keys to generate.
+///
+/// # Returns
+/// A `Vec<Vec<u8>>` of derived keys.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, derive_keys, derive_keys_with_security, Security};
+/// let (pk, _sk) = keypair();
+/// let (_, ss) = encapsulate(&pk);
+/// assert_eq!(derive_keys_with_security(&ss, Security::Bits256, 2), derive_keys(&ss, 2));
+/// ```
+pub fn derive_keys_with_security(shared_secret: &SharedSecret, security: Security, num_keys: usize) -> Vec<Vec<u8>> {
+    match security {
+        Security::Bits128 => {
+            let mut xof = Shake128::default();
+            xof.update(&shared_secret.0);
+            squeeze_keys(xof.finalize_xof(), num_keys, 32)
+        }
+        Security::Bits256 => derive_keys(shared_secret, num_keys),
+    }
+}
+
+/// Fixed-size integers that `derive_packed` can produce from raw XOF output.
+pub trait FromBytes: Sized {
+    /// Number of bytes consumed per value.
+    const SIZE: usize;
+
+    /// Decodes one value from exactly `SIZE` little-endian bytes.
+    fn from_le_slice(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_from_bytes {
+    ($($t:ty),*) => {$(
+        impl FromBytes for $t {
+            const SIZE: usize = core::mem::size_of::<$t>();
+
+            fn from_le_slice(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().expect("slice of SIZE bytes"))
+            }
+        }
+    )*};
+}
+
+impl_from_bytes!(u8, u16, u32);
+
+/// Derives `count` small values from one SHAKE256 expansion of the secret.
+///
+/// Squeezes exactly `count * T::SIZE` bytes and decodes them little-endian, avoiding the
+/// per-key allocation of `derive_keys` when many tiny values are needed (e.g. per-coefficient
+/// sampling randomness). `context` separates independent uses of the same secret.
+///
+/// # Arguments
+/// * `shared_secret` - The `SharedSecret` to derive from.
+/// * `context` - A label for this use.
+/// * `count` - Number of values to derive.
+///
+/// # Returns
+/// A `Vec<T>` of `count` uniformly distributed values.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, derive_packed};
+/// let (pk, _sk) = keypair();
+/// let (_, ss) = encapsulate(&pk);
+/// let values: Vec<u16> = derive_packed(&ss, b"sampling", 8);
+/// assert_eq!(values.len(), 8);
+/// ```
+pub fn derive_packed<T: FromBytes>(shared_secret: &SharedSecret, context: &[u8], count: usize) -> Vec<T> {
+    let mut bytes = vec![0u8; count * T::SIZE];
+    shared_secret.expand(context, &mut bytes);
+    let values = bytes.chunks_exact(T::SIZE).map(T::from_le_slice).collect();
+    bytes.zeroize();
+    values
+}
+
+/// Derives keys by squeezing a SHAKE256 state the caller has already absorbed into.
+///
+/// For protocols that have hashed a transcript into a SHAKE state: continue from it rather
+/// than re-hashing. Absorbing the secret and then `context` gives the same keys as
+/// expanding the secret with that context. `derive_keys` is this with just the secret absorbed.
+///
+/// # Arguments
+/// * `xof` - The absorbed SHAKE256 state; it is finalized here.
+/// * `num_keys` - Number of keys to generate.
+/// * `key_len` - Length of each key in bytes.
+///
+/// # Returns
+/// A `Vec<Vec<u8>>` of `num_keys` consecutive keys from the XOF output.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::derive_keys_from_xof;
+/// use sha3::Shake256;
+/// use sha3::digest::Update;
+/// let mut xof = Shake256::default();
+/// xof.update(b"transcript so far");
+/// let keys = derive_keys_from_xof(xof, 2, 16);
+/// assert_eq!(keys[1].len(), 16);
+/// ```
+pub fn derive_keys_from_xof(xof: Shake256, num_keys: usize, key_len: usize) -> Vec<Vec<u8>> {
+    squeeze_keys(xof.finalize_xof(), num_keys, key_len)
+}
+
+/// Reads `num_keys` consecutive `key_len`-byte keys from `reader`.
+fn squeeze_keys(mut reader: impl XofReader, num_keys: usize, key_len: usize) -> Vec<Vec<u8>> {
+    let mut keys = Vec::new();
     for _ in 0..num_keys {
-        let mut key = vec![0u8; 32];
-        reader.read_exact(&mut key).unwrap();
+        let mut key = vec![0u8; key_len];
+        reader.read(&mut key);
         keys.push(key);
     }
     keys
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The named keys of a secure channel, wiped on drop.
+pub struct ChannelKeys {
+    /// Key for encrypting outgoing traffic.
+    pub tx: [u8; 32],
+    /// Key for decrypting incoming traffic.
+    pub rx: [u8; 32],
+    /// Key for protecting record headers.
+    pub header: [u8; 32],
+}
 
-    #[test]
-    fn test_true_random() {
-        let mut trng = TrueRandom::new();
-        let rand1 = trng.generate(32);
-        let rand2 = trng.generate(32);
-        assert_eq!(rand1.len(), 32);
-        assert_eq!(rand2.len(), 32);
-        assert_ne!(rand1, rand2);
+impl Drop for ChannelKeys {
+    fn drop(&mut self) {
+        self.tx.zeroize();
+        self.rx.zeroize();
+        self.header.zeroize();
     }
+}
 
+/// Derives the transmit, receive and header keys of a channel in one call.
+///
+/// Each key comes from its own domain-separated SHAKE256 expansion of the secret,
+/// so the keys are independent and cannot be mixed up by index.
+///
+/// # Arguments
+/// * `shared_secret` - The `SharedSecret` to derive keys from.
+///
+/// # Returns
+/// The `ChannelKeys` for this secret; the same secret always yields the same keys.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, derive_channel_keys};
+/// let (pk, _sk) = keypair();
+/// let (_, ss) = encapsulate(&pk);
+/// let keys = derive_channel_keys(&ss);
+/// assert_ne!(keys.tx, keys.rx);
+/// ```
+pub fn derive_channel_keys(shared_secret: &SharedSecret) -> ChannelKeys {
+    let mut keys = ChannelKeys {
+        tx: [0u8; 32],
+        rx: [0u8; 32],
+        header: [0u8; 32],
+    };
+    shared_secret.expand(b"ruption-channel-tx", &mut keys.tx);
+    shared_secret.expand(b"ruption-channel-rx", &mut keys.rx);
+    shared_secret.expand(b"ruption-channel-header", &mut keys.header);
+    keys
+}
+
+/// Which end of a channel a party is, for `derive_directional_keys`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// The party that sent the ciphertext and opened the channel.
+    Client,
+    /// The party that decapsulated and accepted the channel.
+    Server,
+}
+
+/// One party's transmit and receive keys, wiped on drop.
+pub struct DirectionalKeys {
+    /// Key for encrypting traffic this party sends.
+    pub tx: [u8; 32],
+    /// Key for decrypting traffic this party receives.
+    pub rx: [u8; 32],
+}
+
+impl Drop for DirectionalKeys {
+    fn drop(&mut self) {
+        self.tx.zeroize();
+        self.rx.zeroize();
+    }
+}
+
+/// Derives the transmit and receive keys for one end of a channel.
+///
+/// One key is derived per direction, client-to-server and server-to-client, and `role`
+/// decides which is `tx`: the client's `tx` is the server's `rx` and vice versa, so the
+/// two sides never encrypt with the same key.
+///
+/// # Arguments
+/// * `shared_secret` - The `SharedSecret` both ends hold.
+/// * `role` - This party's `Role`; the two ends must pass different roles.
+///
+/// # Returns
+/// This party's `DirectionalKeys`.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, decapsulate, derive_directional_keys, Role};
+/// let (pk, sk) = keypair();
+/// let (ct, client_ss) = encapsulate(&pk);
+/// let client = derive_directional_keys(&client_ss, Role::Client);
+/// let server = derive_directional_keys(&decapsulate(&ct, &sk), Role::Server);
+/// assert_eq!(client.tx, server.rx);
+/// assert_ne!(client.tx, client.rx);
+/// ```
+pub fn derive_directional_keys(shared_secret: &SharedSecret, role: Role) -> DirectionalKeys {
+    let mut to_server = [0u8; 32];
+    let mut to_client = [0u8; 32];
+    shared_secret.expand(b"ruption-direction-client-to-server", &mut to_server);
+    shared_secret.expand(b"ruption-direction-server-to-client", &mut to_client);
+    let (tx, rx) = match role {
+        Role::Client => (to_server, to_client),
+        Role::Server => (to_client, to_server),
+    };
+    to_server.zeroize();
+    to_client.zeroize();
+    DirectionalKeys { tx, rx }
+}
+
+/// Computes a non-interactive shared value from my secret key and the other party's public share.
+///
+/// **Toy, not key exchange.** This is a hash-based placeholder, not Diffie–Hellman: the result
+/// depends only on the two public shares, so anyone who has seen both can compute it. It exists
+/// to prototype non-interactive agreement flows until a real NIKE is implemented.
+///
+/// # Arguments
+/// * `my_sk` - My `SecretKey`.
+/// * `their_share` - The other party's `SecretKey::public_share`.
+///
+/// # Returns
+/// The same `SharedSecret` on both sides.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, shared_value};
+/// let (_, alice) = keypair();
+/// let (_, bob) = keypair();
+/// let a = shared_value(&alice, &bob.public_share());
+/// let b = shared_value(&bob, &alice.public_share());
+/// assert_eq!(a.as_bytes(), b.as_bytes());
+/// ```
+pub fn shared_value(my_sk: &SecretKey, their_share: &[u8]) -> SharedSecret {
+    let my_share = my_sk.public_share();
+    let (first, second) = if my_share.as_slice() <= their_share {
+        (my_share.as_slice(), their_share)
+    } else {
+        (their_share, my_share.as_slice())
+    };
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, b"ruption-shared-value-v1");
+    Update::update(&mut hasher, &(first.len() as u64).to_be_bytes());
+    Update::update(&mut hasher, first);
+    Update::update(&mut hasher, second);
+    SharedSecret::new(hasher.finalize().to_vec())
+}
+
+/// Checks in constant time that `proof` was made with the same secret as `theirs`.
+///
+/// `theirs` is the verifier's own copy of the secret; `proof` comes from the other party's
+/// `SharedSecret::agreement_proof` over the same `challenge`.
+///
+/// # Returns
+/// `true` if both parties hold the same shared secret.
+pub fn verify_agreement(theirs: &SharedSecret, challenge: &[u8], proof: &[u8; 32]) -> bool {
+    ct_eq(&theirs.agreement_proof(challenge), proof)
+}
+
+/// Compares two byte slices in constant time.
+///
+/// Slices of different lengths compare unequal, but the common prefix is still compared
+/// in full, so the running time depends only on the lengths, never on the contents.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::ct_eq;
+/// assert!(ct_eq(b"tag", b"tag"));
+/// assert!(!ct_eq(b"tag", b"tap"));
+/// assert!(!ct_eq(b"tag", b"tags"));
+/// ```
+pub fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    let len_eq = (a.len() as u64).ct_eq(&(b.len() as u64));
+    let n = a.len().min(b.len());
+    bool::from(len_eq & a[..n].ct_eq(&b[..n]))
+}
+
+/// Swaps `a` and `b` if `choice` is set, in constant time.
+///
+/// Every byte is touched whether or not the swap happens, so secret permutations (a
+/// Fisher–Yates shuffle over secret indices, the code-based KEM's support permutation) do not
+/// leak through timing or memory access. The slice lengths are public. Nothing in the toy
+/// KEM permutes secret data yet, so it is only compiled for its tests until something does.
+///
+/// # Panics
+/// If `a` and `b` have different lengths.
+#[cfg(test)]
+pub(crate) fn ct_swap(a: &mut [u8], b: &mut [u8], choice: subtle::Choice) {
+    assert_eq!(a.len(), b.len(), "ct_swap needs equal-length slices");
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        subtle::ConditionallySelectable::conditional_swap(x, y, choice);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A deterministic SHAKE256 stream, standing in for a caller's seeded DRBG.
+    struct SeededRng(sha3::Shake256Reader);
+
+    impl SeededRng {
+        fn new(seed: &[u8]) -> Self {
+            let mut xof = Shake256::default();
+            xof.update(seed);
+            SeededRng(xof.finalize_xof())
+        }
+    }
+
+    impl RngCore for SeededRng {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            XofReader::read(&mut self.0, dest);
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_true_random() {
+        let mut trng = TrueRandom::new();
+        let rand1 = trng.generate(32);
+        let rand2 = trng.generate(32);
+        assert_eq!(rand1.len(), 32);
+        assert_eq!(rand2.len(), 32);
+        assert_ne!(rand1, rand2);
+    }
+
+    /// A clock that advances by fixed steps, for deterministic timing.
+    #[cfg(feature = "std")]
+    struct MockClock {
+        base: Instant,
+        elapsed: std::cell::Cell<Duration>,
+    }
+
+    #[cfg(feature = "std")]
+    impl MockClock {
+        fn new() -> Self {
+            MockClock {
+                base: Instant::now(),
+                elapsed: std::cell::Cell::new(Duration::ZERO),
+            }
+        }
+    }
+
+    #[cfg(feature = "std")]
+    impl Clock for MockClock {
+        fn now(&self) -> Instant {
+            self.elapsed.set(self.elapsed.get() + Duration::from_nanos(3));
+            self.base + self.elapsed.get()
+        }
+
+        fn sleep(&self, d: Duration) {
+            self.elapsed.set(self.elapsed.get() + d + Duration::from_nanos(250));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_continuous_health_check_trips_on_stuck_source() {
+        // The mock clock yields the same jitter sample every time: a stuck source.
+        let mut stuck = TrueRandom::with_clock(MockClock::new()).with_continuous_health_check(true);
+        assert!(stuck.try_generate(32).is_ok());
+        assert!(stuck.try_generate(32).is_ok());
+        assert_eq!(stuck.try_generate(32), Err(QuantumError::HealthFailure));
+        assert_eq!(stuck.try_generate(32), Err(QuantumError::HealthFailure));
+
+        let mut unchecked = TrueRandom::with_clock(MockClock::new()).with_continuous_health_check(false);
+        for _ in 0..10 {
+            assert!(unchecked.try_generate(32).is_ok());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_health_check_rejects_stuck_source() {
+        let mut stuck = TrueRandom::with_clock(MockClock::new());
+        assert_eq!(stuck.health_check(), Err(QuantumError::HealthFailure));
+
+        let mut healthy = TrueRandom::new();
+        assert_eq!(healthy.health_check(), Ok(()));
+        // Without jitter only the output is tested, and the mock clock no longer matters.
+        let mut no_jitter =
+            TrueRandom::builder().clock(MockClock::new()).source(EntropySource::QuantumSim).build().unwrap();
+        assert_eq!(no_jitter.health_check(), Ok(()));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_mock_clock_jitter_is_deterministic() {
+        let mut a = TrueRandom::with_clock(MockClock::new());
+        let mut b = TrueRandom::with_clock(MockClock::new());
+        a.collect_jitter().unwrap();
+        b.collect_jitter().unwrap();
+        // Each sample spans one 1ns sleep plus the mock's 250ns and 3ns steps.
+        assert_eq!(a.entropy_pool[64..], [254u8; 10]);
+        assert_eq!(a.entropy_pool[64..], b.entropy_pool[64..]);
+        assert_eq!(a.quantum_sim_entropy().unwrap().len(), 16);
+        // OS seeding still makes the outputs differ.
+        assert_ne!(a.generate(32), b.generate(32));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_seed_from_reader_changes_output() {
+        let mut seeded = TrueRandom::with_clock(MockClock::new());
+        let mut plain = TrueRandom::with_clock(MockClock::new());
+        plain.entropy_pool = seeded.entropy_pool.clone();
+
+        let mut source = std::io::Cursor::new(vec![0x5Au8; 48]);
+        seeded.seed_from_reader(&mut source, 40).unwrap();
+        assert_eq!(seeded.entropy_pool[64..], [0x5A; 40]);
+        assert_ne!(seeded.generate(32), plain.generate(32));
+
+        let pool_len = seeded.entropy_pool.len();
+        let err = seeded.seed_from_reader(&mut source, 40).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(seeded.entropy_pool.len(), pool_len);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_rng_core_fills_arbitrary_lengths() {
+        let mut trng = TrueRandom::new();
+        let mut buf = [0u8; 1000];
+        trng.fill_bytes(&mut buf);
+        assert!(buf.iter().any(|&b| b != 0));
+        assert_ne!(buf[..500], buf[500..]);
+        let mut short = [0u8; 3];
+        assert!(trng.try_fill_bytes(&mut short).is_ok());
+        assert_ne!(trng.next_u32(), trng.next_u32());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fallible_paths_return_results() {
+        let mut trng = TrueRandom::try_new().unwrap();
+        // Longer than one digest, so the output is squeezed from the XOF.
+        assert_eq!(trng.try_generate(1000).unwrap().len(), 1000);
+        assert_eq!(global_rng().try_generate(48).unwrap().len(), 48);
+        let mut reseeding = TrueRandom::builder().reseed_interval(1).build().unwrap();
+        assert!(reseeding.try_generate(32).is_ok());
+        assert_eq!(reseeding.entropy_pool.len(), 32 + 64);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_reseed_caps_pool_and_changes_state() {
+        let mut trng = TrueRandom::with_clock(MockClock::new());
+        for _ in 0..3 {
+            trng.generate(32);
+        }
+        assert!(trng.entropy_pool.len() > 96);
+        trng.reseed().unwrap();
+        assert_eq!(trng.entropy_pool.len(), 32 + 64);
+
+        let before = trng.entropy_pool.clone();
+        trng.reseed_from(b"application entropy");
+        assert_eq!(trng.entropy_pool.len(), 32);
+        let mut twin = TrueRandom::with_clock(MockClock::new());
+        twin.entropy_pool = before;
+        twin.reseed_from(b"other entropy");
+        assert_ne!(trng.entropy_pool, twin.entropy_pool);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_pool_stays_bounded_over_many_draws() {
+        let mut trng = TrueRandom::with_clock(MockClock::new());
+        let mut largest = 0;
+        for _ in 0..100_000 {
+            trng.generate(32);
+            largest = largest.max(trng.entropy_pool.len());
+        }
+        assert!(largest <= POOL_CAP, "pool grew to {largest}");
+        // The allocation has stopped growing too, not just the contents.
+        assert!(trng.entropy_pool.capacity() <= 2 * POOL_CAP);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_fast_skips_jitter() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        /// Counts the clock reads and sleeps a generator makes.
+        struct CountingClock(Arc<AtomicUsize>);
+
+        impl Clock for CountingClock {
+            fn now(&self) -> Instant {
+                self.0.fetch_add(1, Ordering::Relaxed);
+                Instant::now()
+            }
+
+            fn sleep(&self, _d: Duration) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut trng = TrueRandom::with_clock(CountingClock(calls.clone()));
+        let first = trng.generate_fast(64);
+        let pool = trng.entropy_pool.clone();
+        assert_eq!(pool.len(), 32);
+        assert_ne!(trng.generate_fast(64), first);
+        assert_ne!(trng.entropy_pool, pool);
+
+        // The fast path never touches the clock; `generate` samples jitter from it.
+        let before = calls.load(Ordering::Relaxed);
+        trng.generate_fast(64);
+        assert_eq!(calls.load(Ordering::Relaxed), before);
+        trng.generate(64);
+        assert!(calls.load(Ordering::Relaxed) > before);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_quantum_sim_entropy_survives_empty_pool() {
+        let mut trng = TrueRandom::new();
+        trng.entropy_pool.clear();
+        assert_eq!(trng.quantum_sim_entropy().unwrap().len(), 16);
+        assert_eq!(trng.entropy_pool.len(), 64);
+
+        trng.entropy_pool.clear();
+        assert_eq!(trng.generate(32).len(), 32);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_combine_generators() {
+        let mut sources = [
+            TrueRandom::with_clock(MockClock::new()),
+            TrueRandom::with_clock(MockClock::new()),
+            TrueRandom::new(),
+        ];
+        let combined = TrueRandom::combine(&mut sources, 100);
+        assert_eq!(combined.len(), 100);
+        for trng in sources.iter_mut() {
+            assert_ne!(combined[..32], trng.generate(32));
+        }
+        assert_ne!(TrueRandom::combine(&mut sources[..1], 32), TrueRandom::combine(&mut sources[..1], 32));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_generate_hex() {
+        let mut trng = TrueRandom::new();
+        let hex = trng.generate_hex(48);
+        assert_eq!(hex.len(), 96);
+        assert!(hex.bytes().all(|c| c.is_ascii_digit() || (b'a'..=b'f').contains(&c)));
+        assert_eq!(to_hex(&[0x00, 0x9f, 0xff]), "009fff");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_public_key_from_components() {
+        let matrix: Vec<Vec<u8>> = (0..LATTICE_DIM).map(|i| vec![i as u8; LATTICE_DIM + lwe::ROW_EXTRA]).collect();
+        let pk = PublicKey::from_components(matrix.clone(), vec![7u8; CODE_LENGTH]).unwrap();
+        assert_eq!(pk._lattice_matrix, matrix);
+        let (ct, ss) = encapsulate(&pk);
+        assert_eq!(ct.code_cipher.len(), CODE_LENGTH);
+        assert_eq!(ss.as_bytes().len(), 32);
+
+        let mut ragged = matrix.clone();
+        ragged[10].push(0);
+        assert_eq!(
+            PublicKey::from_components(ragged, vec![7u8; CODE_LENGTH]).err(),
+            Some(QuantumError::InvalidLength)
+        );
+        assert!(PublicKey::from_components(matrix[1..].to_vec(), vec![7u8; CODE_LENGTH]).is_err());
+        assert!(PublicKey::from_components(matrix, vec![7u8; CODE_LENGTH - 1]).is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_secret_key_health() {
+        let (_pk, sk) = keypair();
+        assert!(sk.looks_healthy());
+
+        let zero = SecretKey {
+            _lattice_secret: vec![0u8; LATTICE_DIM],
+            _code_secret: vec![0u8; CODE_LENGTH / 8],
+            public_digest: sk.public_digest,
+        };
+        assert!(!zero.looks_healthy());
+
+        let half_stuck = SecretKey {
+            _lattice_secret: sk._lattice_secret.clone(),
+            _code_secret: vec![0xAA; CODE_LENGTH / 8],
+            public_digest: sk.public_digest,
+        };
+        assert!(!half_stuck.looks_healthy());
+
+        let uniform_lattice = SecretKey {
+            _lattice_secret: TrueRandom::new().generate(LATTICE_DIM),
+            _code_secret: sk._code_secret.clone(),
+            public_digest: sk.public_digest,
+        };
+        assert!(!uniform_lattice.looks_healthy());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lattice_secret_coefficients_in_range() {
+        let (_pk, sk) = keypair();
+        assert!(sk.coefficients_in_range(CBD_ETA));
+        assert!(!sk.coefficients_in_range(1));
+        assert!(sk._lattice_secret.iter().any(|&c| c as i8 == -2));
+
+        assert_eq!(sample_cbd(&[0x00, 0xFF, 0x03, 0x0C], 8), [0, 0, 0, 0, 2, 0, 254, 0]);
+        let edge = SecretKey {
+            _lattice_secret: vec![2, 254, 3],
+            _code_secret: Vec::new(),
+            public_digest: sk.public_digest,
+        };
+        assert!(!edge.coefficients_in_range(2));
+        assert!(edge.coefficients_in_range(3));
+    }
+
+    /// Returns whether the most recent wipe of the buffer at `ptr` left it all zero.
+    #[cfg(feature = "std")]
+    fn wiped(ptr: *const u8) -> Option<bool> {
+        WIPES.with(|wipes| wipes.borrow().iter().rev().find(|(at, _)| *at == ptr as usize).map(|&(_, zeroed)| zeroed))
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_secrets_wiped_when_panic_unwinds() {
+        let mut buffers = Vec::new();
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let (pk, sk) = keypair();
+            let (ct, ss) = encapsulate(&pk);
+            let recovered = decapsulate(&ct, &sk);
+            assert!(sk._lattice_secret.iter().any(|&b| b != 0) && ss.0.iter().any(|&b| b != 0));
+            buffers = vec![sk._lattice_secret.as_ptr(), sk._code_secret.as_ptr(), ss.0.as_ptr(), recovered.0.as_ptr()];
+            panic!("operation failed while holding secrets");
+        }));
+        assert!(result.is_err());
+        // Two components of `sk`, plus `ss` and `recovered`, zero before they were freed.
+        for ptr in buffers {
+            assert_eq!(wiped(ptr), Some(true));
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_entropy_pool_wiped_on_drop() {
+        let mut trng = TrueRandom::new();
+        trng.generate(32);
+        assert!(trng.entropy_pool.iter().any(|&b| b != 0));
+        let pool = trng.entropy_pool.as_ptr();
+        drop(trng);
+        assert_eq!(wiped(pool), Some(true), "every byte of the pool allocation must be zero before it is freed");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_secret_key_and_shared_secret_wiped_on_drop() {
+        let (pk, sk) = keypair();
+        let (_ct, ss) = encapsulate(&pk);
+        let buffers = [sk._lattice_secret.as_ptr(), sk._code_secret.as_ptr(), ss.0.as_ptr()];
+        drop(sk);
+        drop(ss);
+        for ptr in buffers {
+            assert_eq!(wiped(ptr), Some(true));
+        }
+    }
+
+    #[cfg(feature = "std")]
     #[test]
     fn test_encryption() {
         let (pk, sk) = keypair();
@@ -303,4 +2527,535 @@ mod tests {
         assert_eq!(keys.len(), 3);
         assert_eq!(keys[0].len(), 32);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_derive_keys_with_security() {
+        let (pk, _sk) = keypair();
+        let (_, ss) = encapsulate(&pk);
+        let fast = derive_keys_with_security(&ss, Security::Bits128, 4);
+        let strong = derive_keys_with_security(&ss, Security::Bits256, 4);
+        assert_eq!(fast.len(), 4);
+        assert!(fast.iter().chain(&strong).all(|k| k.len() == 32));
+        assert_eq!(strong, derive_keys(&ss, 4));
+        assert!(fast.iter().zip(&strong).all(|(f, s)| f != s));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_derive_keys_with_context() {
+        let (pk, _sk) = keypair();
+        let (_, ss) = encapsulate(&pk);
+        let enc = derive_keys_with_context(&ss, b"enc", 2);
+        let mac = derive_keys_with_context(&ss, b"mac", 2);
+        assert_ne!(enc[0], mac[0]);
+        assert_eq!(enc, derive_keys_with_context(&ss, b"enc", 2));
+        assert_eq!(derive_keys_with_context(&ss, b"", 2), derive_keys(&ss, 2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_key_stream_matches_derive_keys() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let expected: Vec<u8> = derive_keys(&ss, 32).concat();
+
+        let mut streamed = vec![0u8; 1000];
+        let mut stream = ss.key_stream();
+        stream.read_exact(&mut streamed[..7]).unwrap();
+        stream.read_exact(&mut streamed[7..]).unwrap();
+        assert!(streamed == expected[..1000]);
+
+        let mut other = vec![0u8; 1000];
+        decapsulate(&ct, &sk).key_stream().read_exact(&mut other).unwrap();
+        assert!(other == streamed, "equal secrets must give equal streams");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_derive_keys_from_absorbed_xof() {
+        let (pk, _sk) = keypair();
+        let (_, ss) = encapsulate(&pk);
+        let mut xof = Shake256::default();
+        xof.update(ss.as_bytes());
+        xof.update(b"handshake transcript");
+        let keys = derive_keys_from_xof(xof.clone(), 3, 24);
+        assert_eq!(keys.len(), 3);
+        assert!(keys.iter().all(|k| k.len() == 24));
+
+        let mut expanded = [0u8; 72];
+        ss.expand(b"handshake transcript", &mut expanded);
+        assert_eq!(keys.concat(), expanded);
+
+        let mut plain = Shake256::default();
+        plain.update(ss.as_bytes());
+        assert_eq!(derive_keys_from_xof(plain, 2, 32), derive_keys(&ss, 2));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_derive_packed_u16() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let values: Vec<u16> = derive_packed(&ss, b"coefficients", 1000);
+        assert_eq!(values.len(), 1000);
+        assert_eq!(values, derive_packed::<u16>(&decapsulate(&ct, &sk), b"coefficients", 1000));
+        assert_ne!(values, derive_packed::<u16>(&ss, b"other", 1000));
+        // A prefix of the expansion gives a prefix of the values.
+        assert_eq!(derive_packed::<u16>(&ss, b"coefficients", 10), values[..10]);
+
+        let mut bytes = [0u8; 4];
+        ss.expand(b"coefficients", &mut bytes);
+        assert_eq!(values[0], u16::from_le_bytes([bytes[0], bytes[1]]));
+        assert_eq!(derive_packed::<u32>(&ss, b"coefficients", 1)[0], u32::from_le_bytes(bytes));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_try_decapsulate_checks_dimensions() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        assert_eq!(try_decapsulate(&ct, &sk).unwrap().as_bytes(), ss.as_bytes());
+
+        let (big_pk, _) = keypair_with_params(&Params::new(LATTICE_DIM, 2 * CODE_LENGTH).unwrap());
+        let (big_ct, _) = encapsulate(&big_pk);
+        assert_eq!(try_decapsulate(&big_ct, &sk).err(), Some(QuantumError::InvalidLength));
+    }
+
+    #[cfg(feature = "fault-injection")]
+    #[test]
+    fn test_injected_faults_propagate() {
+        let mut trng = TrueRandom::new();
+        trng.fail_next_generate();
+        assert_eq!(trng.try_generate(32), Err(QuantumError::EntropyUnavailable));
+        assert_eq!(trng.try_generate(32).unwrap().len(), 32);
+        trng.fail_next_generate();
+        assert!(std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| trng.generate(8))).is_err());
+
+        let (pk, sk) = keypair();
+        let (ct, _ss) = encapsulate(&pk);
+        fail_next_decapsulate();
+        assert_eq!(try_decapsulate(&ct, &sk).err(), Some(QuantumError::DecapsulationFailed));
+        assert!(try_decapsulate(&ct, &sk).is_ok());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_beacon_binding() {
+        let (pk, sk) = keypair();
+        let (ct, ss1) = encapsulate_with_beacon(&pk, b"round-1");
+        let ss2 = decapsulate_with_beacon(&ct, &sk, b"round-1");
+        assert_eq!(ss1.as_bytes(), ss2.as_bytes());
+
+        let ss3 = decapsulate_with_beacon(&ct, &sk, b"round-2");
+        assert_ne!(ss1.as_bytes(), ss3.as_bytes());
+        assert_ne!(ss1.as_bytes(), decapsulate(&ct, &sk).as_bytes());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_streaming_decapsulation() {
+        let (pk, sk) = keypair();
+        let (ct, ss1) = encapsulate(&pk);
+        let bytes = ct.to_bytes();
+
+        let mut trng = TrueRandom::new();
+        let mut stream = DecapsulationStream::new(&sk);
+        let mut rest = &bytes[..];
+        while !rest.is_empty() {
+            let take = (trng.generate(1)[0] as usize % 97 + 1).min(rest.len());
+            stream.update(&rest[..take]);
+            rest = &rest[take..];
+        }
+        let ss2 = stream.finalize().unwrap();
+        assert_eq!(ss1.as_bytes(), ss2.as_bytes());
+        assert_eq!(decapsulate(&ct, &sk).as_bytes(), ss2.as_bytes());
+
+        let mut stream = DecapsulationStream::new(&sk);
+        stream.update(&bytes);
+        stream.update(&[0]);
+        assert_eq!(stream.finalize().err(), Some(QuantumError::Deserialize));
+
+        let mut stream = DecapsulationStream::new(&sk);
+        stream.update(&bytes[..bytes.len() - 1]);
+        assert_eq!(stream.finalize().err(), Some(QuantumError::Deserialize));
+
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] ^= 1;
+        let mut stream = DecapsulationStream::new(&sk);
+        stream.update(&bad_magic);
+        assert_eq!(stream.finalize().err(), Some(QuantumError::Deserialize));
+
+        // The raw components without the header and prefixes are not an encoding.
+        let mut stream = DecapsulationStream::new(&sk);
+        stream.update(&ct.lattice_cipher);
+        stream.update(&ct.code_cipher);
+        assert!(stream.finalize().is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_nonce_for_counter() {
+        let (pk, _sk) = keypair();
+        let (_, ss) = encapsulate(&pk);
+        let nonces: std::collections::HashSet<[u8; 12]> = (0..1000).map(|i| ss.nonce_for(i)).collect();
+        assert_eq!(nonces.len(), 1000);
+        assert_eq!(ss.nonce_for(42), ss.nonce_for(42));
+        assert_ne!(ss.nonce_for(u64::MAX), ss.nonce_for(0));
+
+        // Under a counter XOR, nonces 0 and 1 would differ exactly as nonces 2 and 3 do.
+        let diff = |a: u64, b: u64| -> Vec<u8> {
+            ss.nonce_for(a).iter().zip(ss.nonce_for(b)).map(|(x, y)| x ^ y).collect()
+        };
+        assert_ne!(diff(0, 1), diff(2, 3));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_channel_keys_distinct_and_deterministic() {
+        let (pk, sk) = keypair();
+        let (ct, ss1) = encapsulate(&pk);
+        let ss2 = decapsulate(&ct, &sk);
+        let keys = derive_channel_keys(&ss1);
+        assert_ne!(keys.tx, keys.rx);
+        assert_ne!(keys.tx, keys.header);
+        assert_ne!(keys.rx, keys.header);
+
+        let again = derive_channel_keys(&ss2);
+        assert_eq!(keys.tx, again.tx);
+        assert_eq!(keys.rx, again.rx);
+        assert_eq!(keys.header, again.header);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_key_and_ciphertext_bytes_round_trip() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+
+        let pk_bytes = pk.to_bytes();
+        assert_eq!(PublicKey::from_bytes(&pk_bytes).unwrap(), pk);
+        let restored = SecretKey::from_bytes(&sk.to_bytes()).unwrap();
+        assert_eq!(restored.to_bytes(), sk.to_bytes());
+        assert_eq!(decapsulate(&ct, &restored).as_bytes(), ss.as_bytes());
+        assert_eq!(Ciphertext::from_bytes(&ct.to_bytes()).unwrap(), ct);
+
+        for bytes in [pk_bytes, sk.to_bytes().to_vec(), ct.to_bytes()] {
+            let truncated = &bytes[..bytes.len() - 1];
+            let mut over_long = bytes.clone();
+            over_long.push(0);
+            for bad in [truncated, &over_long[..], &[][..]] {
+                assert!(PublicKey::from_bytes(bad).is_err());
+                assert!(SecretKey::from_bytes(bad).is_err());
+                assert!(Ciphertext::from_bytes(bad).is_err());
+            }
+        }
+        // Well-framed input with the wrong dimensions.
+        let mut short_row = Vec::new();
+        codec::put_u32(&mut short_row, 1);
+        codec::put_bytes(&mut short_row, &[0u8; 3]);
+        codec::put_bytes(&mut short_row, &[0u8; CODE_LENGTH]);
+        assert_eq!(PublicKey::from_bytes(&short_row), Err(QuantumError::InvalidLength));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_wrong_secret_key_gives_different_secret() {
+        let (alice_pk, alice_sk) = keypair();
+        let (_bob_pk, bob_sk) = keypair();
+        let (ct, ss) = encapsulate(&alice_pk);
+        assert_eq!(decapsulate(&ct, &alice_sk).as_bytes(), ss.as_bytes());
+        assert_ne!(decapsulate(&ct, &bob_sk).as_bytes(), ss.as_bytes());
+
+        let mut stream = DecapsulationStream::new(&bob_sk);
+        stream.update(&ct.to_bytes());
+        assert_eq!(stream.finalize().unwrap().as_bytes(), decapsulate(&ct, &bob_sk).as_bytes());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_encapsulate_batch_three_recipients() {
+        let recipients: Vec<(PublicKey, SecretKey)> = (0..3).map(|_| keypair()).collect();
+        let pks: Vec<PublicKey> = recipients.iter().map(|(pk, _)| pk.clone()).collect();
+        let batch = encapsulate_batch(&pks);
+        assert_eq!(batch.len(), 3);
+        for (i, (ct, ss)) in batch.iter().enumerate() {
+            assert!(decapsulate(ct, &recipients[i].1) == *ss);
+            assert!(decapsulate(ct, &recipients[(i + 1) % 3].1) != *ss);
+        }
+        assert!(encapsulate_batch::<PublicKey>(&[]).is_empty());
+        let borrowed = encapsulate_batch(&[&pks[0]]);
+        assert!(decapsulate(&borrowed[0].0, &recipients[0].1) == borrowed[0].1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_lattice_matrix_rows_are_distinct() {
+        let (pk, _sk) = keypair();
+        let rows: std::collections::HashSet<&Vec<u8>> = pk._lattice_matrix.iter().collect();
+        assert_eq!(rows.len(), LATTICE_DIM);
+
+        // One draw for the CBD bits and one for the code secret; the rest is derived.
+        let mut draws = 0;
+        let (constant_pk, _sk) = keypair_from(&Params::toy(), |len| {
+            draws += 1;
+            vec![0x11; len]
+        });
+        assert_eq!(draws, 2);
+        let rows: std::collections::HashSet<&Vec<u8>> = constant_pk._lattice_matrix.iter().collect();
+        assert_eq!(rows.len(), LATTICE_DIM);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_fingerprints_are_stable_and_distinct() {
+        let (pk1, _sk1) = keypair();
+        let (pk2, _sk2) = keypair();
+        assert_ne!(pk1.fingerprint(), pk2.fingerprint());
+        let decoded = PublicKey::from_bytes(&pk1.to_bytes()).unwrap();
+        assert_eq!(decoded.fingerprint(), pk1.fingerprint());
+        assert_eq!(pk1.fingerprint_hex(), to_hex(&Sha3_256::digest(pk1.to_bytes())[..16]));
+
+        // Fixed key, fixed fingerprint: catches any change to the encoding or the hash.
+        let (fixed, _sk) = keypair_from(&Params::toy(), |len| vec![0x5A; len]);
+        assert_eq!(fixed.fingerprint_hex(), "062f8d86198ae523686c955914de6e75");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_public_key_derived_from_secret() {
+        let (pk, sk) = keypair();
+        let derived = PublicKey::from_secret(&sk);
+        assert_eq!(derived.to_bytes(), pk.to_bytes());
+        assert_eq!(public_digest(&derived), sk.public_digest);
+
+        let (other_pk, other_sk) = keypair_with_params(&Params::new(64, 128).unwrap());
+        assert_eq!(PublicKey::from_secret(&other_sk), other_pk);
+        assert_ne!(PublicKey::from_secret(&other_sk), pk);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_public_values_do_not_determine_shared_secret() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let digest = public_digest(&pk);
+
+        // Everything an eavesdropper holds, hashed without the encrypted message.
+        assert!(shared_secret_from(&ct.lattice_cipher, &ct.code_cipher, &digest, &[]) != ss);
+        // A key carrying every public value and the right code secret, but another LWE secret.
+        let (_, other) = keypair();
+        let impostor = SecretKey {
+            _lattice_secret: other._lattice_secret.clone(),
+            _code_secret: sk._code_secret.clone(),
+            public_digest: digest,
+        };
+        assert!(decapsulate(&ct, &impostor) != ss);
+        assert!(decapsulate(&ct, &sk) == ss);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_keys_carry_params_and_refuse_other_levels() {
+        let small = Params::new(32, 64).unwrap();
+        let (small_pk, small_sk) = keypair_with_params(&small);
+        assert_eq!((small_pk.params(), small_sk.params()), (small, small));
+        let restored = PublicKey::from_bytes(&small_pk.to_bytes()).unwrap();
+        assert_eq!(restored.params(), small);
+        assert_eq!(SecretKey::from_bytes(&small_sk.to_bytes()).unwrap().params(), small);
+
+        let (ct, ss) = encapsulate_with_params(&small_pk, &small).unwrap();
+        assert_eq!(ct.params(), small);
+        assert!(try_decapsulate(&ct, &small_sk).unwrap() == ss);
+        assert_eq!(encapsulate_with_params(&small_pk, &Params::toy()).err(), Some(QuantumError::InvalidParams));
+
+        let (_toy_pk, toy_sk) = keypair();
+        assert_eq!(try_decapsulate(&ct, &toy_sk).err(), Some(QuantumError::InvalidLength));
+        let mut stream = DecapsulationStream::new(&small_sk);
+        stream.update(&ct.to_bytes());
+        assert!(stream.finalize().unwrap() == ss);
+        let mut stream = DecapsulationStream::new(&toy_sk);
+        stream.update(&ct.to_bytes());
+        assert_eq!(stream.finalize().err(), Some(QuantumError::InvalidLength));
+    }
+
+    #[test]
+    fn test_caller_supplied_rng_is_reproducible() {
+        let (pk, sk) = keypair_with_rng(&mut SeededRng::new(b"seed"));
+        let (pk2, sk2) = keypair_with_rng(&mut SeededRng::new(b"seed"));
+        assert_eq!(pk.to_bytes(), pk2.to_bytes());
+        assert_eq!(sk.to_bytes(), sk2.to_bytes());
+        assert_ne!(keypair_with_rng(&mut SeededRng::new(b"other")).0, pk);
+
+        let (ct, ss) = encapsulate_with_rng(&pk, &mut SeededRng::new(b"encaps"));
+        let (ct2, ss2) = encapsulate_with_rng(&pk, &mut SeededRng::new(b"encaps"));
+        assert_eq!(ct, ct2);
+        assert!(ss == ss2);
+        assert!(decapsulate(&ct, &sk) == ss);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shared_secret_constant_time_eq() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let recovered = decapsulate(&ct, &sk);
+        assert!(ss.ct_eq(&recovered));
+        assert!(ss == recovered);
+
+        let (_, other) = encapsulate(&pk);
+        assert!(!ss.ct_eq(&other));
+        assert!(ss != other);
+        // A difference in the last byte only is still caught.
+        let mut last = ss.as_bytes().to_vec();
+        last[SHARED_SECRET_LEN - 1] ^= 1;
+        assert!(!ss.ct_eq(&SharedSecret::new(last)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shared_secret_len() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        assert_eq!(ss.as_bytes().len(), SHARED_SECRET_LEN);
+        assert_eq!(ss.len(), SHARED_SECRET_LEN);
+        assert!(!ss.is_empty());
+        assert_eq!(decapsulate(&ct, &sk).len(), SHARED_SECRET_LEN);
+        assert_eq!(Params::toy().sizes().shared_secret, SHARED_SECRET_LEN);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_directional_keys_mirror() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let client = derive_directional_keys(&ss, Role::Client);
+        let server = derive_directional_keys(&decapsulate(&ct, &sk), Role::Server);
+        assert_eq!(client.tx, server.rx);
+        assert_eq!(client.rx, server.tx);
+        assert_ne!(client.tx, client.rx);
+        assert_ne!(client.tx, derive_channel_keys(&ss).tx);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_shared_value_agreement() {
+        let (_, alice) = keypair();
+        let (_, bob) = keypair();
+        let (_, carol) = keypair();
+        assert_eq!(alice.public_share(), alice.public_share());
+
+        let ab = shared_value(&alice, &bob.public_share());
+        let ba = shared_value(&bob, &alice.public_share());
+        assert_eq!(ab.as_bytes(), ba.as_bytes());
+        assert_ne!(ab.as_bytes(), shared_value(&alice, &carol.public_share()).as_bytes());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_agreement_proof() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let (_, other) = encapsulate(&pk);
+        let challenge = TrueRandom::new().generate(32);
+
+        let proof = ss.agreement_proof(&challenge);
+        assert!(verify_agreement(&decapsulate(&ct, &sk), &challenge, &proof));
+        assert!(!verify_agreement(&other, &challenge, &proof));
+        assert!(!verify_agreement(&ss, b"stale challenge", &proof));
+        assert_ne!(proof, other.agreement_proof(&challenge));
+    }
+
+    #[test]
+    fn test_ct_swap() {
+        let (mut a, mut b) = ([1u8, 2, 3], [7u8, 8, 9]);
+        ct_swap(&mut a, &mut b, subtle::Choice::from(0));
+        assert_eq!((a, b), ([1, 2, 3], [7, 8, 9]));
+        ct_swap(&mut a, &mut b, subtle::Choice::from(1));
+        assert_eq!((a, b), ([7, 8, 9], [1, 2, 3]));
+        ct_swap(&mut [], &mut [], subtle::Choice::from(1));
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(ct_eq(&[1, 2, 3], &[1, 2, 3]));
+        assert!(ct_eq(&[], &[]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2, 4]));
+        assert!(!ct_eq(&[0, 2, 3], &[1, 2, 3]));
+        assert!(!ct_eq(&[1, 2, 3], &[1, 2]));
+        assert!(!ct_eq(&[1, 2], &[1, 2, 3]));
+        assert!(!ct_eq(&[], &[0]));
+    }
+
+    fn fixed_ciphertext(lattice: &[u8], code: &[u8]) -> Ciphertext {
+        Ciphertext::from_parts(lattice.to_vec(), code.to_vec()).unwrap()
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ciphertext_canonical_encoding() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        let bytes = ct.to_bytes();
+        assert_eq!(bytes.len(), CIPHERTEXT_HEADER_LEN + 8 + lwe::cipher_len(LATTICE_DIM) + CODE_LENGTH);
+        assert_eq!(&bytes[..5], b"RQE1\x01");
+        assert!(Ciphertext::is_canonical(&bytes));
+        let decoded = Ciphertext::from_bytes_strict(&bytes).unwrap();
+        assert_eq!(decapsulate(&decoded, &sk).as_bytes(), ss.as_bytes());
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(!Ciphertext::is_canonical(&trailing));
+        assert_eq!(Ciphertext::from_bytes_strict(&trailing).err(), Some(QuantumError::Deserialize));
+
+        // Same bytes, but one moved from the code component into the lattice component.
+        let mut shifted = bytes[..CIPHERTEXT_HEADER_LEN].to_vec();
+        codec::put_bytes(&mut shifted, &[&ct.lattice_cipher[..], &ct.code_cipher[..1]].concat());
+        codec::put_bytes(&mut shifted, &ct.code_cipher[1..]);
+        assert!(!Ciphertext::is_canonical(&shifted));
+        assert_eq!(Ciphertext::from_bytes_strict(&shifted).err(), Some(QuantumError::InvalidLength));
+        assert!(!Ciphertext::is_canonical(&bytes[..bytes.len() - 1]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_ciphertext_rejects_unknown_magic_and_version() {
+        let (pk, _sk) = keypair();
+        let (ct, _ss) = encapsulate(&pk);
+        let bytes = ct.to_bytes();
+        assert_eq!(Ciphertext::from_bytes(&bytes).unwrap(), ct);
+
+        let mut wrong_magic = bytes.clone();
+        wrong_magic[..4].copy_from_slice(b"RQE0");
+        assert_eq!(Ciphertext::from_bytes(&wrong_magic), Err(QuantumError::Deserialize));
+
+        let mut future = bytes.clone();
+        future[4] = CIPHERTEXT_VERSION + 1;
+        assert_eq!(Ciphertext::from_bytes(&future), Err(QuantumError::Deserialize));
+
+        // Bytes in the old, headerless layout must not parse either.
+        assert_eq!(Ciphertext::from_bytes(&bytes[CIPHERTEXT_HEADER_LEN..]), Err(QuantumError::Deserialize));
+        assert_eq!(Ciphertext::from_bytes(b"RQE"), Err(QuantumError::Deserialize));
+    }
+
+    #[test]
+    fn test_decapsulate_golden_vector() {
+        let lattice: Vec<u8> = (0..lwe::cipher_len(LATTICE_DIM)).map(|i| i as u8).collect();
+        let code: Vec<u8> = (0..CODE_LENGTH).map(|i| (i * 7) as u8).collect();
+        let ct = fixed_ciphertext(&lattice, &code);
+        // The secret depends on the key pair, so the key is fixed too.
+        let (_pk, sk) = keypair_from(&Params::toy(), |len| vec![0x5A; len]);
+
+        let ss = decapsulate(&ct, &sk);
+        assert_eq!(ss.as_bytes(), decapsulate(&ct, &sk).as_bytes());
+        assert_eq!(
+            ss.as_bytes(),
+            &[
+                0x09, 0xcf, 0x89, 0x01, 0xe9, 0x90, 0xad, 0x65, 0xbf, 0x17, 0x0b, 0x55, 0xb4, 0x00, 0xe4, 0x81,
+                0x20, 0x5d, 0x46, 0xb1, 0x02, 0xec, 0x3d, 0xea, 0x1e, 0xee, 0x79, 0x6d, 0x3d, 0xdf, 0x79, 0xe7,
+            ]
+        );
+
+        assert!(Ciphertext::from_parts(lattice, code[1..].to_vec()).is_err());
+    }
 }
\ No newline at end of file
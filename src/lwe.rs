@@ -0,0 +1,224 @@
+//! Toy LWE public-key encryption behind the KEM's lattice component.
+//!
+//! A FrodoKEM-shaped scheme modulo `q = 2^16`, so all arithmetic is wrapping `u16`. The public
+//! matrix `A` is `n × n` with byte entries, the secret `S` and error `E` are `n × 8` matrices of
+//! CBD(2) coefficients derived from the secret key, and each public key row carries its row of
+//! `B = A·S + E`. A 256-bit message is hidden four bits per entry of an 8×8 block as
+//! `C1 = S'·A + E'` and `C2 = S'·B + E'' + encode(m)`; `C2 − C1·S` leaves the message plus
+//! error far below the decoding margin of `q / 32`.
+//!
+//! The sizes and distributions show the structure only; they are not a vetted parameter set.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::aead::absorb_framed;
+use crate::sample_cbd;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use zeroize::{Zeroize, Zeroizing};
+
+const WIDTH: usize = 8;              // Columns of S and B, rows of S' and C1
+const BLOCK: usize = WIDTH * WIDTH;  // Entries of C2, one message nibble each
+const HALF_STEP: u16 = 1 << 11;      // Half the spacing between encoded nibbles
+/// Bytes of `B` appended to each public key row: `WIDTH` big-endian `u16` entries.
+pub(crate) const ROW_EXTRA: usize = 2 * WIDTH;
+/// Length of the message encrypted under the public key.
+pub(crate) const MESSAGE_LEN: usize = BLOCK / 2;
+
+/// Returns the length of the lattice ciphertext `C1 ‖ C2` for dimension `n`.
+pub(crate) const fn cipher_len(n: usize) -> usize {
+    2 * WIDTH * n + 2 * BLOCK
+}
+
+/// Returns the dimension whose `cipher_len` is `len`, or 0 if there is none.
+pub(crate) fn dim_for_cipher_len(len: usize) -> usize {
+    match len.checked_sub(2 * BLOCK) {
+        Some(rest) if rest.is_multiple_of(2 * WIDTH) => rest / (2 * WIDTH),
+        _ => 0,
+    }
+}
+
+/// Appends to each row of `A` its row of `B = A·S + E` for the key's `lattice_secret`.
+pub(crate) fn append_public_columns(rows: &mut [Vec<u8>], lattice_secret: &[u8]) {
+    let n = lattice_secret.len();
+    let secret = secret_matrix(lattice_secret);
+    let error = squeeze_cbd(&mut key_reader(b"ruption-lwe-key-error-v1", lattice_secret), n * WIDTH);
+    for (j, row) in rows.iter_mut().enumerate() {
+        let mut b = [0u16; WIDTH];
+        for (c, entry) in b.iter_mut().enumerate() {
+            *entry = error[j * WIDTH + c];
+        }
+        for (k, &a) in row[..n].iter().enumerate() {
+            for (c, entry) in b.iter_mut().enumerate() {
+                *entry = entry.wrapping_add(u16::from(a).wrapping_mul(secret[k * WIDTH + c]));
+            }
+        }
+        for entry in b {
+            row.extend_from_slice(&entry.to_be_bytes());
+        }
+    }
+}
+
+/// Encrypts `message` to the public key rows `[A | B]`.
+///
+/// The coins are derived from the message and the key's digest, so encryption is
+/// deterministic given the message.
+pub(crate) fn encrypt(rows: &[Vec<u8>], message: &[u8; MESSAGE_LEN], public_digest: &[u8; 32]) -> Vec<u8> {
+    let n = rows.len();
+    let mut xof = Shake256::default();
+    xof.update(b"ruption-lwe-coins-v1");
+    absorb_framed(&mut xof, message);
+    absorb_framed(&mut xof, public_digest);
+    let mut coins = xof.finalize_xof();
+    let ephemeral = squeeze_cbd(&mut coins, WIDTH * n);
+    let mut c1 = squeeze_cbd(&mut coins, WIDTH * n);
+    let mut c2 = squeeze_cbd(&mut coins, BLOCK);
+
+    for (e, entry) in c2.iter_mut().enumerate() {
+        let nibble = (message[e / 2] >> (4 * (e % 2))) & 0xf;
+        *entry = entry.wrapping_add(u16::from(nibble) << 12);
+    }
+    for (j, row) in rows.iter().enumerate() {
+        let (a, b) = row.split_at(n);
+        for i in 0..WIDTH {
+            let s = ephemeral[i * n + j];
+            for (k, &a) in a.iter().enumerate() {
+                c1[i * n + k] = c1[i * n + k].wrapping_add(s.wrapping_mul(u16::from(a)));
+            }
+            for c in 0..WIDTH {
+                let b = u16::from_be_bytes([b[2 * c], b[2 * c + 1]]);
+                c2[i * WIDTH + c] = c2[i * WIDTH + c].wrapping_add(s.wrapping_mul(b));
+            }
+        }
+    }
+
+    let mut out = Vec::with_capacity(cipher_len(n));
+    for entry in c1.iter().chain(c2.iter()) {
+        out.extend_from_slice(&entry.to_be_bytes());
+    }
+    out
+}
+
+/// Recovers the message from `C1 ‖ C2` with the key's `lattice_secret`.
+///
+/// A ciphertext for another key or of the wrong length yields an unrelated message.
+pub(crate) fn decrypt(lattice_cipher: &[u8], lattice_secret: &[u8]) -> Zeroizing<[u8; MESSAGE_LEN]> {
+    let mut decryptor = Decryptor::new(lattice_secret);
+    decryptor.update(lattice_cipher);
+    decryptor.finish()
+}
+
+/// Incremental `decrypt`, accumulating `C2 − C1·S` as the ciphertext bytes arrive.
+pub(crate) struct Decryptor {
+    secret: Zeroizing<Vec<u16>>,      // S, n rows of WIDTH entries
+    block: Zeroizing<[u16; BLOCK]>,   // Running C2 − C1·S
+    entries: usize,                   // Ciphertext entries absorbed so far
+    high_byte: Option<u8>,            // First byte of an entry split across updates
+}
+
+impl Decryptor {
+    /// Starts decrypting with the key's `lattice_secret`.
+    pub(crate) fn new(lattice_secret: &[u8]) -> Self {
+        Decryptor {
+            secret: secret_matrix(lattice_secret),
+            block: Zeroizing::new([0u16; BLOCK]),
+            entries: 0,
+            high_byte: None,
+        }
+    }
+
+    /// Absorbs the next bytes of `C1 ‖ C2`; bytes past its end are ignored.
+    pub(crate) fn update(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            match self.high_byte.take() {
+                None => self.high_byte = Some(byte),
+                Some(high) => self.absorb(u16::from_be_bytes([high, byte])),
+            }
+        }
+    }
+
+    /// Decodes the message from the accumulated block.
+    pub(crate) fn finish(self) -> Zeroizing<[u8; MESSAGE_LEN]> {
+        let mut message = Zeroizing::new([0u8; MESSAGE_LEN]);
+        for (e, &entry) in self.block.iter().enumerate() {
+            let nibble = (entry.wrapping_add(HALF_STEP) >> 12) as u8;
+            message[e / 2] |= nibble << (4 * (e % 2));
+        }
+        message
+    }
+
+    fn absorb(&mut self, value: u16) {
+        let n = self.secret.len() / WIDTH;
+        let e = self.entries;
+        if e < WIDTH * n {
+            // Entry (i, k) of C1 contributes C1[i][k] · S[k][c] to every column c of row i.
+            let (i, k) = (e / n, e % n);
+            for c in 0..WIDTH {
+                let product = value.wrapping_mul(self.secret[k * WIDTH + c]);
+                self.block[i * WIDTH + c] = self.block[i * WIDTH + c].wrapping_sub(product);
+            }
+        } else if e < WIDTH * n + BLOCK {
+            let index = e - WIDTH * n;
+            self.block[index] = self.block[index].wrapping_add(value);
+        }
+        self.entries += 1;
+    }
+}
+
+/// Derives `S` from the key's lattice secret, one row of `WIDTH` entries per coefficient.
+fn secret_matrix(lattice_secret: &[u8]) -> Zeroizing<Vec<u16>> {
+    squeeze_cbd(&mut key_reader(b"ruption-lwe-secret-v1", lattice_secret), lattice_secret.len() * WIDTH)
+}
+
+/// Returns the SHAKE256 reader for `label` over the key's lattice secret.
+fn key_reader(label: &[u8], lattice_secret: &[u8]) -> impl XofReader {
+    let mut xof = Shake256::default();
+    xof.update(label);
+    absorb_framed(&mut xof, lattice_secret);
+    xof.finalize_xof()
+}
+
+/// Reads `count` CBD(2) coefficients from `reader` as wrapping `u16` values.
+fn squeeze_cbd(reader: &mut impl XofReader, count: usize) -> Zeroizing<Vec<u16>> {
+    let mut bits = vec![0u8; count.div_ceil(2)];
+    reader.read(&mut bits);
+    let mut coefficients = sample_cbd(&bits, count);
+    bits.zeroize();
+    let values = coefficients.iter().map(|&c| c as i8 as i16 as u16).collect();
+    coefficients.zeroize();
+    Zeroizing::new(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cipher_len_round_trips_and_rejects_other_lengths() {
+        for n in [1, 256, 4096] {
+            assert_eq!(dim_for_cipher_len(cipher_len(n)), n);
+            assert_eq!(dim_for_cipher_len(cipher_len(n) + 1), 0);
+        }
+        assert_eq!(dim_for_cipher_len(2 * BLOCK - 1), 0);
+    }
+
+    #[test]
+    fn test_decrypt_recovers_message_and_tracks_c2() {
+        let lattice_secret = sample_cbd(&[0x93; 64], 128);
+        let mut rows: Vec<Vec<u8>> = (0..128).map(|j| (0..128).map(|k| (j * 31 + k * 17) as u8).collect()).collect();
+        append_public_columns(&mut rows, &lattice_secret);
+        let message: [u8; MESSAGE_LEN] = core::array::from_fn(|i| (i * 37) as u8);
+        let mut cipher = encrypt(&rows, &message, &[7u8; 32]);
+        assert_eq!(cipher.len(), cipher_len(128));
+        assert_eq!(*decrypt(&cipher, &lattice_secret), message);
+
+        // Adding q/16 to the first C2 entry adds one to the first nibble and nothing else.
+        let c2 = cipher_len(128) - 2 * BLOCK;
+        let shifted = u16::from_be_bytes([cipher[c2], cipher[c2 + 1]]).wrapping_add(1 << 12);
+        cipher[c2..c2 + 2].copy_from_slice(&shifted.to_be_bytes());
+        let mut expected = message;
+        expected[0] = (expected[0] & 0xf0) | (expected[0].wrapping_add(1) & 0x0f);
+        assert_eq!(*decrypt(&cipher, &lattice_secret), expected);
+        assert_ne!(*decrypt(&cipher, &sample_cbd(&[0x39; 64], 128)), expected);
+    }
+}
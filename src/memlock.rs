@@ -0,0 +1,67 @@
+//! Optional locking of secret buffers in RAM, so they are never written to swap.
+//!
+//! Active only with the `mlock` feature on Unix; elsewhere both calls are no-ops. Locking is
+//! best effort: if it fails, a warning is printed once and the buffer stays unlocked.
+//! The kernel locks whole pages and does not count nested locks, so unlocking one secret
+//! can unlock a page it shares with another.
+
+#[cfg(all(feature = "mlock", unix))]
+mod imp {
+    use std::sync::Once;
+
+    static WARN: Once = Once::new();
+
+    pub(crate) fn lock(bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        // SAFETY: mlock only changes paging attributes of the range, which `bytes` borrows.
+        let rc = unsafe { libc::mlock(bytes.as_ptr().cast(), bytes.len()) };
+        if rc != 0 {
+            WARN.call_once(|| {
+                eprintln!(
+                    "ruption_quantum_encrypt: mlock failed ({}); secrets may be swapped to disk",
+                    std::io::Error::last_os_error()
+                );
+            });
+        }
+    }
+
+    pub(crate) fn unlock(ptr: *const u8, len: usize) {
+        if len == 0 {
+            return;
+        }
+        // SAFETY: munlock never dereferences the range; a range that was never locked, or is
+        // not mapped, just makes it return an error, which is ignored.
+        unsafe {
+            libc::munlock(ptr.cast(), len);
+        }
+    }
+}
+
+#[cfg(not(all(feature = "mlock", unix)))]
+mod imp {
+    pub(crate) fn lock(_bytes: &[u8]) {}
+
+    pub(crate) fn unlock(_ptr: *const u8, _len: usize) {}
+}
+
+/// Locks the pages holding `bytes`.
+pub(crate) use imp::lock;
+/// Unlocks `len` bytes at `ptr`; takes a raw region because callers zeroize (and clear) first.
+pub(crate) use imp::unlock;
+
+#[cfg(all(test, feature = "mlock", unix))]
+mod tests {
+    use crate::{decapsulate, encapsulate, keypair};
+
+    #[test]
+    fn test_locked_secrets_construct_and_drop() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        assert_eq!(decapsulate(&ct, &sk).as_bytes(), ss.as_bytes());
+        assert!(sk.looks_healthy());
+        drop(sk);
+        drop(ss);
+    }
+}
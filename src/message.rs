@@ -0,0 +1,240 @@
+//! Symmetric message encryption under a key from `derive_keys`.
+//!
+//! Each message gets a fresh random nonce, so one key can encrypt many messages. The layout
+//! is the nonce, then the SHAKE256-keystream ciphertext, then a keyed SHA3-256 tag over both.
+//! The streaming functions produce and accept the same layout, so a stream encrypted with
+//! `encrypt_stream` can be opened with `decrypt` and vice versa.
+
+#[cfg(feature = "std")]
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::aead::{self, TAG_LEN};
+use crate::QuantumError;
+#[cfg(feature = "std")]
+use crate::fill_random;
+#[cfg(feature = "std")]
+use crate::ct_eq;
+#[cfg(feature = "std")]
+use sha3::digest::Update;
+#[cfg(feature = "std")]
+use std::io::{ErrorKind, Read, Write};
+#[cfg(feature = "std")]
+use zeroize::Zeroizing;
+
+/// Length of the random nonce that prefixes every message.
+const NONCE_LEN: usize = 16;
+/// Associated data binding messages to this API, so they never open as another format.
+const MESSAGE_AAD: &[u8] = b"ruption-message-v1";
+/// Bytes read from the source per step of the streaming functions.
+#[cfg(feature = "std")]
+const CHUNK_LEN: usize = 64 * 1024;
+
+/// Encrypts and authenticates `plaintext` under `key`.
+///
+/// # Arguments
+/// * `key` - The symmetric key, e.g. one of the keys from `derive_keys`.
+/// * `plaintext` - The message to encrypt.
+///
+/// # Returns
+/// The nonce, ciphertext and tag, `plaintext.len() + 48` bytes in all.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, derive_keys, encrypt, decrypt};
+/// let (pk, _sk) = keypair();
+/// let (_, ss) = encapsulate(&pk);
+/// let key = &derive_keys(&ss, 1)[0];
+/// let sealed = encrypt(key, b"hello");
+/// assert_eq!(decrypt(key, &sealed).unwrap(), b"hello");
+/// ```
+#[cfg(feature = "std")]
+pub fn encrypt(key: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut nonce = [0u8; NONCE_LEN];
+    fill_random(&mut nonce);
+    let mut out = nonce.to_vec();
+    out.extend(aead::seal(key, &nonce, MESSAGE_AAD, plaintext));
+    out
+}
+
+/// Verifies and decrypts a message from `encrypt`.
+///
+/// The tag is checked in constant time before any plaintext is produced.
+///
+/// # Returns
+/// The plaintext, `QuantumError::AuthenticationFailed` if the key is wrong or the message was
+/// modified, or `QuantumError::InvalidLength` if it is too short to be a message.
+pub fn decrypt(key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, QuantumError> {
+    if ciphertext.len() < NONCE_LEN + TAG_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    let (nonce, sealed) = ciphertext.split_at(NONCE_LEN);
+    aead::open(key, nonce, MESSAGE_AAD, sealed)
+}
+
+/// Encrypts everything read from `src` under `key`, writing the message to `dst` as it goes.
+///
+/// Memory use is one fixed-size chunk however long the input is. The output is exactly what
+/// `encrypt` would produce for the whole input with the same nonce.
+///
+/// # Arguments
+/// * `key` - The symmetric key.
+/// * `src` - The plaintext source, read to the end.
+/// * `dst` - Receives the nonce, the ciphertext and finally the tag.
+///
+/// # Returns
+/// `Ok(())` once the tag is written and `dst` flushed, or `QuantumError::Io` if either side
+/// fails; `dst` then holds an incomplete message.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{decrypt_stream, encrypt_stream};
+/// let key = [7u8; 32];
+/// let mut sealed = Vec::new();
+/// encrypt_stream(&key, &b"large file"[..], &mut sealed).unwrap();
+/// let mut opened = Vec::new();
+/// decrypt_stream(&key, &sealed[..], &mut opened).unwrap();
+/// assert_eq!(opened, b"large file");
+/// ```
+#[cfg(feature = "std")]
+pub fn encrypt_stream<R: Read, W: Write>(key: &[u8], mut src: R, mut dst: W) -> Result<(), QuantumError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    fill_random(&mut nonce);
+    dst.write_all(&nonce).map_err(io_error)?;
+    let mut stream = aead::keystream(key, &nonce);
+    let mut mac = aead::start_tag(key, &nonce, MESSAGE_AAD);
+    let mut chunk = Zeroizing::new(vec![0u8; CHUNK_LEN]);
+    let mut total = 0u64;
+    loop {
+        let len = read_chunk(&mut src, &mut chunk)?;
+        if len == 0 {
+            break;
+        }
+        let chunk = &mut chunk[..len];
+        aead::apply_keystream(&mut stream, chunk);
+        Update::update(&mut mac, chunk);
+        dst.write_all(chunk).map_err(io_error)?;
+        total += len as u64;
+    }
+    dst.write_all(&aead::finish_tag(mac, total)).map_err(io_error)?;
+    dst.flush().map_err(io_error)
+}
+
+/// Decrypts a message read from `src`, writing the plaintext to `dst` as it goes.
+///
+/// The tag comes last, so plaintext reaches `dst` before it can be verified. On any error
+/// the data already written is unauthenticated and must be discarded, e.g. by writing to a
+/// temporary file and renaming it only after `Ok(())`.
+///
+/// # Returns
+/// `Ok(())` once the tag has been verified in constant time;
+/// `QuantumError::AuthenticationFailed` if the key is wrong or the message was modified;
+/// `QuantumError::InvalidLength` if it is too short to be a message;
+/// or `QuantumError::Io` if reading or writing fails.
+#[cfg(feature = "std")]
+pub fn decrypt_stream<R: Read, W: Write>(key: &[u8], mut src: R, mut dst: W) -> Result<(), QuantumError> {
+    let mut nonce = [0u8; NONCE_LEN];
+    if read_chunk(&mut src, &mut nonce)? < NONCE_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    let mut stream = aead::keystream(key, &nonce);
+    let mut mac = aead::start_tag(key, &nonce, MESSAGE_AAD);
+    // The last TAG_LEN bytes seen so far may be the tag, so they stay held back in `buf`.
+    let mut buf = Zeroizing::new(vec![0u8; TAG_LEN + CHUNK_LEN]);
+    let mut held = 0;
+    let mut total = 0u64;
+    loop {
+        let len = read_chunk(&mut src, &mut buf[held..])?;
+        held += len;
+        let ready = held.saturating_sub(TAG_LEN);
+        if ready > 0 {
+            let chunk = &mut buf[..ready];
+            Update::update(&mut mac, chunk);
+            aead::apply_keystream(&mut stream, chunk);
+            dst.write_all(chunk).map_err(io_error)?;
+            buf.copy_within(ready..held, 0);
+            held -= ready;
+            total += ready as u64;
+        }
+        if len == 0 {
+            break;
+        }
+    }
+    if held < TAG_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    if !ct_eq(&aead::finish_tag(mac, total), &buf[..TAG_LEN]) {
+        return Err(QuantumError::AuthenticationFailed);
+    }
+    dst.flush().map_err(io_error)
+}
+
+/// Reads until `buf` is full or `src` is exhausted, returning the number of bytes read.
+#[cfg(feature = "std")]
+fn read_chunk(src: &mut impl Read, buf: &mut [u8]) -> Result<usize, QuantumError> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match src.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(len) => filled += len,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(io_error(err)),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(feature = "std")]
+fn io_error(err: std::io::Error) -> QuantumError {
+    QuantumError::Io(err.kind())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_and_tamper() {
+        let key = [7u8; 32];
+        let sealed = encrypt(&key, b"attack at dawn");
+        assert_eq!(sealed.len(), 14 + NONCE_LEN + TAG_LEN);
+        assert_eq!(decrypt(&key, &sealed).unwrap(), b"attack at dawn");
+        assert_ne!(encrypt(&key, b"attack at dawn"), sealed, "nonces must differ");
+
+        for i in [0, NONCE_LEN, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert_eq!(decrypt(&key, &tampered), Err(QuantumError::AuthenticationFailed));
+        }
+        assert_eq!(decrypt(&[8u8; 32], &sealed), Err(QuantumError::AuthenticationFailed));
+        assert_eq!(decrypt(&key, &sealed[..NONCE_LEN + TAG_LEN - 1]), Err(QuantumError::InvalidLength));
+        assert_eq!(decrypt(&key, &encrypt(&key, b"")).unwrap(), b"");
+    }
+
+    #[test]
+    fn test_stream_round_trip_over_cursor() {
+        use std::io::Cursor;
+
+        let key = [9u8; 32];
+        let plaintext: Vec<u8> = (0..3 * 1024 * 1024 + 17).map(|i| (i % 251) as u8).collect();
+        let mut sealed = Cursor::new(Vec::new());
+        encrypt_stream(&key, Cursor::new(&plaintext), &mut sealed).unwrap();
+        let sealed = sealed.into_inner();
+        assert_eq!(sealed.len(), plaintext.len() + NONCE_LEN + TAG_LEN);
+
+        let mut opened = Cursor::new(Vec::new());
+        decrypt_stream(&key, Cursor::new(&sealed), &mut opened).unwrap();
+        assert!(opened.into_inner() == plaintext);
+        assert!(decrypt(&key, &sealed).unwrap() == plaintext, "stream and message layouts must match");
+
+        let mut tampered = sealed.clone();
+        tampered[CHUNK_LEN + 5] ^= 1;
+        let result = decrypt_stream(&key, Cursor::new(&tampered), std::io::sink());
+        assert_eq!(result, Err(QuantumError::AuthenticationFailed));
+        let short = &sealed[..NONCE_LEN + TAG_LEN - 1];
+        assert_eq!(decrypt_stream(&key, short, std::io::sink()), Err(QuantumError::InvalidLength));
+
+        let mut small = Vec::new();
+        decrypt_stream(&key, &encrypt(&key, b"hi")[..], &mut small).unwrap();
+        assert_eq!(small, b"hi");
+    }
+}
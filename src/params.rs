@@ -0,0 +1,223 @@
+//! Parameter sets for the KEM and the byte sizes they produce.
+
+use crate::lwe;
+use crate::{QuantumError, CIPHERTEXT_HEADER_LEN, CODE_LENGTH, LATTICE_DIM, SHARED_SECRET_LEN};
+#[cfg(feature = "std")]
+use crate::{ct_eq, decapsulate, encapsulate, keypair_with_params};
+
+/// Parameter set for the hybrid lattice/code-based scheme.
+///
+/// Use one of the presets: `toy` matches the crate's built-in demonstration sizes,
+/// `recommended` and `paranoid` follow the larger dimensions suggested for real security.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::Params;
+/// let sizes = Params::recommended().sizes();
+/// assert!(sizes.public_key > Params::toy().sizes().public_key);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Params {
+    lattice_dim: usize,           // Lattice dimension
+    code_length: usize,           // Code length in bits
+}
+
+/// Serialized byte sizes of the KEM objects for a given parameter set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KemSizes {
+    /// Length of `PublicKey::to_bytes`.
+    pub public_key: usize,
+    /// Length of `SecretKey::to_bytes`.
+    pub secret_key: usize,
+    /// Length of `Ciphertext::to_bytes`.
+    pub ciphertext: usize,
+    /// Length of a `SharedSecret`.
+    pub shared_secret: usize,
+}
+
+impl Params {
+    /// Creates a custom parameter set.
+    ///
+    /// The secret key holds `code_length / 8` bytes of code-based material, so `code_length`
+    /// must be a positive multiple of 8; `lattice_dim` must be positive.
+    ///
+    /// # Returns
+    /// The validated `Params`, or `QuantumError::InvalidParams`.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::Params;
+    /// assert!(Params::new(512, 1024).is_ok());
+    /// assert!(Params::new(512, 1025).is_err());
+    /// ```
+    pub fn new(lattice_dim: usize, code_length: usize) -> Result<Self, QuantumError> {
+        if lattice_dim == 0 || code_length == 0 || !code_length.is_multiple_of(8) {
+            return Err(QuantumError::InvalidParams);
+        }
+        Ok(Params {
+            lattice_dim,
+            code_length,
+        })
+    }
+
+    /// Builds parameters from object dimensions that callers have already validated.
+    pub(crate) const fn from_dims(lattice_dim: usize, code_length: usize) -> Self {
+        Params {
+            lattice_dim,
+            code_length,
+        }
+    }
+
+    /// Returns `true` if these dimensions would pass `Params::new`.
+    pub(crate) fn is_valid(&self) -> bool {
+        Params::new(self.lattice_dim, self.code_length).is_ok()
+    }
+
+    /// The toy demonstration parameters used by `keypair()` and `encapsulate()`.
+    pub const fn toy() -> Self {
+        Params {
+            lattice_dim: LATTICE_DIM,
+            code_length: CODE_LENGTH,
+        }
+    }
+
+    /// Parameters at the sizes suggested for real-world security.
+    pub const fn recommended() -> Self {
+        Params {
+            lattice_dim: 2048,
+            code_length: 8192,
+        }
+    }
+
+    /// Oversized parameters for users who want an extra margin at a large space cost.
+    pub const fn paranoid() -> Self {
+        Params {
+            lattice_dim: 4096,
+            code_length: 16384,
+        }
+    }
+
+    /// Returns the lattice dimension.
+    pub fn lattice_dim(&self) -> usize {
+        self.lattice_dim
+    }
+
+    /// Returns the code length in bits.
+    pub fn code_length(&self) -> usize {
+        self.code_length
+    }
+
+    /// Computes the sizes of keys, ciphertexts and shared secrets for these parameters.
+    ///
+    /// Lets callers compare the space cost of the presets without generating any keys.
+    ///
+    /// # Returns
+    /// A `KemSizes` with the encoded size of each object, including its framing.
+    pub fn sizes(&self) -> KemSizes {
+        // Row count, then each row of A and B and the code generator behind a u32 length.
+        let row = self.lattice_dim + lwe::ROW_EXTRA;
+        let public_key = 4 + self.lattice_dim * (4 + row) + 4 + self.code_length;
+        KemSizes {
+            public_key,
+            secret_key: 4 + self.lattice_dim + 4 + self.code_length / 8 + 32,
+            ciphertext: CIPHERTEXT_HEADER_LEN + 4 + lwe::cipher_len(self.lattice_dim) + 4 + self.code_length,
+            shared_secret: SHARED_SECRET_LEN,
+        }
+    }
+}
+
+impl Default for Params {
+    /// Defaults to the `toy` parameters used by the no-argument API.
+    fn default() -> Self {
+        Self::toy()
+    }
+}
+
+/// Estimates how often decapsulation fails to recover the encapsulated secret.
+///
+/// Each trial generates a fresh keypair for `params`, encapsulates, decapsulates and compares
+/// the two secrets. Use enough trials to resolve the rate you care about: with `n` trials
+/// and no failures, the true rate is only known to be below roughly `3 / n`. The toy LWE
+/// noise stays many standard deviations inside its decoding margin at every preset, so any
+/// nonzero rate here indicates a bug.
+///
+/// # Arguments
+/// * `params` - The parameter set to measure.
+/// * `trials` - Number of independent keypair/encapsulation rounds.
+///
+/// # Returns
+/// The observed fraction of mismatched secrets, or `0.0` if `trials` is zero.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{measure_decaps_failure_rate, Params};
+/// assert_eq!(measure_decaps_failure_rate(&Params::toy(), 10), 0.0);
+/// ```
+#[cfg(feature = "std")]
+pub fn measure_decaps_failure_rate(params: &Params, trials: usize) -> f64 {
+    if trials == 0 {
+        return 0.0;
+    }
+    let failures = (0..trials)
+        .filter(|_| {
+            let (pk, sk) = keypair_with_params(params);
+            let (ct, ss) = encapsulate(&pk);
+            !ct_eq(decapsulate(&ct, &sk).as_bytes(), ss.as_bytes())
+        })
+        .count();
+    failures as f64 / trials as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paranoid_sizes_exceed_toy() {
+        let toy = Params::toy().sizes();
+        let paranoid = Params::paranoid().sizes();
+        assert!(paranoid.public_key > toy.public_key);
+        assert!(paranoid.secret_key > toy.secret_key);
+        assert!(paranoid.ciphertext > toy.ciphertext);
+        // The shared secret is a fixed-size hash output at every level.
+        assert_eq!(paranoid.shared_secret, toy.shared_secret);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_sizes_match_encoded_objects() {
+        for params in [Params::toy(), Params::recommended(), Params::paranoid()] {
+            let sizes = params.sizes();
+            let (pk, sk) = keypair_with_params(&params);
+            let (ct, ss) = encapsulate(&pk);
+            assert_eq!(sizes.public_key, pk.to_bytes().len(), "{params:?}");
+            assert_eq!(sizes.secret_key, sk.to_bytes().len(), "{params:?}");
+            assert_eq!(sizes.ciphertext, ct.to_bytes().len(), "{params:?}");
+            assert_eq!(sizes.shared_secret, ss.as_bytes().len(), "{params:?}");
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_recommended_decaps_failure_rate() {
+        let rate = measure_decaps_failure_rate(&Params::recommended(), 8);
+        assert!(rate < 0.01, "failure rate {rate}");
+        assert_eq!(measure_decaps_failure_rate(&Params::recommended(), 0), 0.0);
+
+        let (pk, sk) = keypair_with_params(&Params::recommended());
+        let (ct, _) = encapsulate(&pk);
+        assert_eq!(sk._code_secret.len(), 8192 / 8);
+        assert_eq!(ct.to_bytes().len(), Params::recommended().sizes().ciphertext);
+    }
+
+    #[test]
+    fn test_invalid_params_rejected() {
+        assert_eq!(Params::new(256, 513), Err(QuantumError::InvalidParams));
+        assert_eq!(Params::new(0, 512), Err(QuantumError::InvalidParams));
+        assert_eq!(Params::new(256, 0), Err(QuantumError::InvalidParams));
+        assert_eq!(Params::new(256, 512), Ok(Params::toy()));
+        for preset in [Params::toy(), Params::recommended(), Params::paranoid()] {
+            assert_eq!(Params::new(preset.lattice_dim(), preset.code_length()), Ok(preset));
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Key derivation from a human passphrase.
+//!
+//! The password, salt and iteration count are hashed once, then the state is rehashed with
+//! SHA3-256 `iterations` times, so each guess costs an attacker the same work it costs the
+//! caller. Store the salt and iteration count next to the ciphertext; neither is secret.
+
+use crate::aead::absorb_framed;
+#[cfg(feature = "std")]
+use crate::fill_random;
+use sha3::digest::Update;
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroize;
+
+/// Length in bytes of the salts returned by `generate_salt`.
+pub const SALT_LEN: usize = 16;
+
+/// Derives a 32-byte key from `password` and `salt`.
+///
+/// The same `(password, salt, iterations)` always gives the same key. Pick `iterations` so
+/// one derivation takes as long as you can afford on your slowest device; 100 000 or more is
+/// a reasonable floor. This is a plain iterated hash, not a memory-hard function, so it slows
+/// down but does not stop a well-equipped attacker with a weak password.
+///
+/// # Arguments
+/// * `password` - The passphrase bytes.
+/// * `salt` - A random salt, e.g. from `generate_salt`; use a fresh one per key.
+/// * `iterations` - Rounds of SHA3-256 after the initial hash.
+///
+/// # Returns
+/// The derived key.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{derive_key_from_password, generate_salt, encrypt, decrypt};
+/// let salt = generate_salt();
+/// let key = derive_key_from_password(b"correct horse battery staple", &salt, 10_000);
+/// let sealed = encrypt(&key, b"diary");
+/// let again = derive_key_from_password(b"correct horse battery staple", &salt, 10_000);
+/// assert_eq!(decrypt(&again, &sealed).unwrap(), b"diary");
+/// ```
+pub fn derive_key_from_password(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, b"ruption-password-kdf-v1");
+    absorb_framed(&mut hasher, password);
+    absorb_framed(&mut hasher, salt);
+    Update::update(&mut hasher, &iterations.to_be_bytes());
+    let mut state: [u8; 32] = hasher.finalize().into();
+
+    for round in 0..iterations {
+        let mut hasher = Sha3_256::new();
+        Update::update(&mut hasher, &state);
+        Update::update(&mut hasher, &round.to_be_bytes());
+        state.zeroize();
+        state = hasher.finalize().into();
+    }
+    state
+}
+
+/// Generates a random salt for `derive_key_from_password`.
+///
+/// # Returns
+/// `SALT_LEN` bytes from the crate's `TrueRandom` generator.
+///
+/// # Panics
+/// If the randomness source fails.
+#[cfg(feature = "std")]
+pub fn generate_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    fill_random(&mut salt);
+    salt
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_key_is_deterministic_and_salted() {
+        let salt = generate_salt();
+        let key = derive_key_from_password(b"hunter2", &salt, 1000);
+        assert_eq!(derive_key_from_password(b"hunter2", &salt, 1000), key);
+
+        let mut other_salt = salt;
+        other_salt[0] ^= 1;
+        assert_ne!(derive_key_from_password(b"hunter2", &other_salt, 1000), key);
+        assert_ne!(derive_key_from_password(b"hunter3", &salt, 1000), key);
+        assert_ne!(derive_key_from_password(b"hunter2", &salt, 1001), key);
+        assert_ne!(generate_salt(), salt);
+    }
+}
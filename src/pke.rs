@@ -0,0 +1,96 @@
+//! Single-recipient public-key encryption with authenticated metadata.
+//!
+//! A blob is the encoded KEM ciphertext, then the length-prefixed metadata, then the body
+//! sealed under a key derived from the encapsulated secret. The ciphertext and metadata are
+//! the body's associated data, so the metadata is readable but cannot be altered.
+
+use alloc::vec::Vec;
+use crate::aead;
+#[cfg(feature = "std")]
+use crate::codec::put_bytes;
+use crate::codec::take_prefixed;
+#[cfg(feature = "std")]
+use crate::{encapsulate, PublicKey};
+use crate::{decapsulate, Ciphertext, QuantumError, SecretKey, SharedSecret};
+use zeroize::Zeroizing;
+
+const BODY_NONCE: &[u8] = b"ruption-pke-body-v1";
+
+/// Encrypts `plaintext` to `pk`, attaching optional cleartext `metadata`.
+///
+/// # Arguments
+/// * `pk` - The recipient's `PublicKey`.
+/// * `plaintext` - The message to encrypt.
+/// * `metadata` - Data such as a content type, stored unencrypted but authenticated.
+///
+/// # Returns
+/// The sealed blob.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encrypt_to, decrypt_from};
+/// let (pk, sk) = keypair();
+/// let blob = encrypt_to(&pk, b"{\"hi\":1}", Some(b"application/json"));
+/// let (plaintext, metadata) = decrypt_from(&blob, &sk).unwrap();
+/// assert_eq!(plaintext, b"{\"hi\":1}");
+/// assert_eq!(metadata, b"application/json");
+/// ```
+#[cfg(feature = "std")]
+pub fn encrypt_to(pk: &PublicKey, plaintext: &[u8], metadata: Option<&[u8]>) -> Vec<u8> {
+    let (ct, ss) = encapsulate(pk);
+    let mut blob = Vec::new();
+    put_bytes(&mut blob, &ct.to_bytes());
+    put_bytes(&mut blob, metadata.unwrap_or_default());
+    let body = aead::seal(&body_key(&ss)[..], BODY_NONCE, &blob, plaintext);
+    blob.extend(body);
+    blob
+}
+
+/// Decrypts a blob from `encrypt_to`.
+///
+/// # Returns
+/// `(plaintext, metadata)`, with empty metadata if none was attached;
+/// `QuantumError::AuthenticationFailed` if the blob, including its metadata, was modified or
+/// `sk` is not the recipient's key;
+/// or `QuantumError::Deserialize` if it is malformed.
+pub fn decrypt_from(blob: &[u8], sk: &SecretKey) -> Result<(Vec<u8>, Vec<u8>), QuantumError> {
+    let mut rest = blob;
+    let ct = Ciphertext::from_bytes_strict(take_prefixed(&mut rest)?)?;
+    let metadata = take_prefixed(&mut rest)?;
+    let header = &blob[..blob.len() - rest.len()];
+    let plaintext = aead::open(&body_key(&decapsulate(&ct, sk))[..], BODY_NONCE, header, rest)?;
+    Ok((plaintext, metadata.to_vec()))
+}
+
+fn body_key(ss: &SharedSecret) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    ss.expand(b"ruption-pke-body-key", &mut key[..]);
+    key
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::keypair;
+
+    #[test]
+    fn test_metadata_round_trip_and_tamper() {
+        let (pk, sk) = keypair();
+        let blob = encrypt_to(&pk, b"report body", Some(b"text/plain"));
+        let (plaintext, metadata) = decrypt_from(&blob, &sk).unwrap();
+        assert_eq!(plaintext, b"report body");
+        assert_eq!(metadata, b"text/plain");
+
+        let metadata_at = blob.windows(10).position(|w| w == b"text/plain").unwrap();
+        let mut tampered = blob.clone();
+        tampered[metadata_at] = b'T';
+        assert_eq!(decrypt_from(&tampered, &sk), Err(QuantumError::AuthenticationFailed));
+
+        let (_other_pk, other_sk) = keypair();
+        assert_eq!(decrypt_from(&blob, &other_sk), Err(QuantumError::AuthenticationFailed));
+
+        let bare = encrypt_to(&pk, b"", None);
+        assert_eq!(decrypt_from(&bare, &sk).unwrap(), (Vec::new(), Vec::new()));
+        assert_eq!(decrypt_from(&bare[..10], &sk), Err(QuantumError::Deserialize));
+    }
+}
@@ -0,0 +1,153 @@
+//! Statistical checks of randomness samples, for operators recording entropy assessments.
+//!
+//! The tests flag grossly broken sources only; passing them does not prove a source is sound.
+
+/// Minimum acceptable min-entropy estimate, in bits per byte.
+const MIN_ENTROPY_THRESHOLD: f64 = 5.0;
+/// Largest acceptable magnitude of the serial-correlation coefficient.
+const SERIAL_CORRELATION_LIMIT: f64 = 0.1;
+/// Standard normal quantile for the upper-tail chi-square test (alpha = 0.001).
+const CHI_SQUARE_Z: f64 = 3.090;
+
+/// Statistical summary of a randomness sample.
+///
+/// Produced by [`entropy_quality_report`] for operators who need to record their own entropy assessments.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityReport {
+    /// Number of bytes analysed.
+    pub sample_len: usize,
+    /// Most-common-value min-entropy estimate (SP 800-90B style), in bits per byte.
+    pub min_entropy: f64,
+    /// Chi-square statistic of the byte histogram against a uniform distribution (255 degrees of freedom).
+    pub chi_square: f64,
+    /// Whether the chi-square statistic stays below the critical value at alpha = 0.001.
+    pub chi_square_passed: bool,
+    /// Serial-correlation coefficient between consecutive bytes; near zero for random data.
+    pub serial_correlation: f64,
+}
+
+impl QualityReport {
+    /// Returns `true` if the estimated min-entropy is below the acceptable threshold.
+    pub fn low_entropy(&self) -> bool {
+        self.min_entropy < MIN_ENTROPY_THRESHOLD
+    }
+
+    /// Returns `true` if every statistic is within its acceptable range.
+    pub fn passed(&self) -> bool {
+        !self.low_entropy()
+            && self.chi_square_passed
+            && self.serial_correlation.abs() < SERIAL_CORRELATION_LIMIT
+    }
+}
+
+/// Computes entropy-quality statistics over a sample, e.g. one taken from `TrueRandom::generate`.
+///
+/// Reports a min-entropy estimate, a chi-square uniformity result, and the serial-correlation coefficient.
+/// The statistics are only meaningful for samples of a few kilobytes or more.
+///
+/// # Arguments
+/// * `sample` - The bytes to analyse.
+///
+/// # Returns
+/// A `QualityReport` describing the sample.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{entropy_quality_report, TrueRandom};
+/// let mut trng = TrueRandom::new();
+/// let report = entropy_quality_report(&trng.generate(8192));
+/// assert_eq!(report.sample_len, 8192);
+/// ```
+pub fn entropy_quality_report(sample: &[u8]) -> QualityReport {
+    let n = sample.len();
+    let mut counts = [0u64; 256];
+    for &byte in sample {
+        counts[byte as usize] += 1;
+    }
+
+    let chi_square = chi_square(&counts, n);
+    QualityReport {
+        sample_len: n,
+        min_entropy: min_entropy(&counts, n),
+        chi_square,
+        chi_square_passed: chi_square < chi_square_critical(255.0),
+        serial_correlation: serial_correlation(sample),
+    }
+}
+
+/// Most-common-value estimate with a 99% upper confidence bound on the mode probability.
+fn min_entropy(counts: &[u64; 256], n: usize) -> f64 {
+    if n < 2 {
+        return 0.0;
+    }
+    let n = n as f64;
+    let p_hat = *counts.iter().max().unwrap_or(&0) as f64 / n;
+    let p_upper = (p_hat + 2.576 * (p_hat * (1.0 - p_hat) / (n - 1.0)).sqrt()).min(1.0);
+    -p_upper.log2()
+}
+
+fn chi_square(counts: &[u64; 256], n: usize) -> f64 {
+    if n == 0 {
+        return 0.0;
+    }
+    let expected = n as f64 / 256.0;
+    counts
+        .iter()
+        .map(|&c| {
+            let d = c as f64 - expected;
+            d * d / expected
+        })
+        .sum()
+}
+
+/// Wilson–Hilferty approximation of the upper-tail chi-square critical value.
+fn chi_square_critical(df: f64) -> f64 {
+    let a = 2.0 / (9.0 * df);
+    df * (1.0 - a + CHI_SQUARE_Z * a.sqrt()).powi(3)
+}
+
+/// Serial-correlation coefficient with wraparound, as computed by the classic `ent` tool.
+fn serial_correlation(sample: &[u8]) -> f64 {
+    let n = sample.len();
+    if n < 2 {
+        return 0.0;
+    }
+    let (mut sum, mut sum_sq, mut sum_lag) = (0.0f64, 0.0f64, 0.0f64);
+    for i in 0..n {
+        let x = sample[i] as f64;
+        let next = sample[(i + 1) % n] as f64;
+        sum += x;
+        sum_sq += x * x;
+        sum_lag += x * next;
+    }
+    let n = n as f64;
+    let denominator = n * sum_sq - sum * sum;
+    if denominator == 0.0 {
+        // A constant sample is perfectly correlated with itself.
+        return 1.0;
+    }
+    (n * sum_lag - sum * sum) / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TrueRandom;
+
+    #[test]
+    fn test_quality_report_flags_biased_sample() {
+        let biased: Vec<u8> = (0..8192u32).map(|i| if i % 10 == 0 { (i % 256) as u8 } else { 0 }).collect();
+        let report = entropy_quality_report(&biased);
+        assert!(report.low_entropy());
+        assert!(!report.chi_square_passed);
+        assert!(!report.passed());
+    }
+
+    #[test]
+    fn test_quality_report_passes_random_sample() {
+        let mut trng = TrueRandom::new();
+        let report = entropy_quality_report(&trng.generate(16384));
+        assert!(!report.low_entropy(), "{report:?}");
+        assert!(report.passed(), "{report:?}");
+    }
+}
@@ -0,0 +1,123 @@
+//! Sealed storage for a set of derived keys.
+//!
+//! A blob is a random nonce, then the key count and the length-prefixed keys, encrypted and
+//! authenticated under a key derived from the master `SharedSecret`. Only the holder of that
+//! secret can open it, and any change to the blob is detected.
+
+use alloc::vec::Vec;
+use crate::aead;
+#[cfg(feature = "std")]
+use crate::codec::{put_bytes, put_u32};
+use crate::codec::{take_prefixed, take_u32};
+#[cfg(feature = "std")]
+use crate::random_bytes;
+use crate::{QuantumError, SharedSecret};
+use zeroize::{Zeroize, Zeroizing};
+
+const NONCE_LEN: usize = 16;
+const SCHEDULE_AAD: &[u8] = b"ruption-key-schedule-v1";
+
+/// Seals a derived key schedule under a key derived from `master`.
+///
+/// Lets applications cache an expensive derivation and reload it later without re-running the KEM.
+///
+/// # Arguments
+/// * `keys` - The keys to store, e.g. the output of `derive_keys`.
+/// * `master` - The `SharedSecret` the sealing key is derived from.
+///
+/// # Returns
+/// A random nonce followed by the authenticated, encrypted key set.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, derive_keys, seal_key_schedule, open_key_schedule};
+/// let (pk, _sk) = keypair();
+/// let (_, ss) = encapsulate(&pk);
+/// let keys = derive_keys(&ss, 4);
+/// let blob = seal_key_schedule(&keys, &ss);
+/// assert_eq!(open_key_schedule(&blob, &ss).unwrap(), keys);
+/// ```
+#[cfg(feature = "std")]
+pub fn seal_key_schedule(keys: &[Vec<u8>], master: &SharedSecret) -> Vec<u8> {
+    let mut serialized = Zeroizing::new(Vec::new());
+    put_u32(&mut serialized, keys.len());
+    for key in keys {
+        put_bytes(&mut serialized, key);
+    }
+
+    let nonce = random_bytes(NONCE_LEN);
+    let sealing_key = sealing_key(master);
+    let mut blob = nonce.clone();
+    blob.extend(aead::seal(&sealing_key[..], &nonce, SCHEDULE_AAD, &serialized));
+    blob
+}
+
+/// Restores a key schedule sealed by `seal_key_schedule`.
+///
+/// # Returns
+/// The original keys, `QuantumError::AuthenticationFailed` if the blob was tampered with or
+/// `master` is wrong, or `QuantumError::Deserialize` if the blob is malformed.
+pub fn open_key_schedule(blob: &[u8], master: &SharedSecret) -> Result<Vec<Vec<u8>>, QuantumError> {
+    if blob.len() < NONCE_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    let (nonce, sealed) = blob.split_at(NONCE_LEN);
+    let sealing_key = sealing_key(master);
+    let serialized = Zeroizing::new(aead::open(&sealing_key[..], nonce, SCHEDULE_AAD, sealed)?);
+
+    let mut rest = &serialized[..];
+    let count = take_u32(&mut rest)? as usize;
+    let mut keys: Vec<Vec<u8>> = Vec::with_capacity(count.min(rest.len() / 4));
+    for _ in 0..count {
+        match take_prefixed(&mut rest) {
+            Ok(key) => keys.push(key.to_vec()),
+            Err(err) => {
+                keys.zeroize();
+                return Err(err);
+            }
+        }
+    }
+    if !rest.is_empty() {
+        keys.zeroize();
+        return Err(QuantumError::Deserialize);
+    }
+    Ok(keys)
+}
+
+fn sealing_key(master: &SharedSecret) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    master.expand(b"ruption-key-schedule-seal", &mut key[..]);
+    key
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{derive_keys, encapsulate, keypair};
+
+    #[test]
+    fn test_key_schedule_round_trip() {
+        let (pk, _sk) = keypair();
+        let (_, ss) = encapsulate(&pk);
+        let mut keys = derive_keys(&ss, 5);
+        keys.push(vec![0xAB; 7]);
+        keys.push(Vec::new());
+
+        let blob = seal_key_schedule(&keys, &ss);
+        assert_eq!(open_key_schedule(&blob, &ss).unwrap(), keys);
+        assert_eq!(open_key_schedule(&seal_key_schedule(&[], &ss), &ss).unwrap(), Vec::<Vec<u8>>::new());
+    }
+
+    #[test]
+    fn test_key_schedule_rejects_wrong_master() {
+        let (pk, _sk) = keypair();
+        let (_, ss) = encapsulate(&pk);
+        let (_, other) = encapsulate(&pk);
+        let blob = seal_key_schedule(&derive_keys(&ss, 3), &ss);
+        assert_eq!(open_key_schedule(&blob, &other), Err(QuantumError::AuthenticationFailed));
+
+        let mut tampered = blob.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(open_key_schedule(&tampered, &ss), Err(QuantumError::AuthenticationFailed));
+    }
+}
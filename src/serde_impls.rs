@@ -0,0 +1,140 @@
+//! `Serialize`/`Deserialize` for keys, ciphertexts and shared secrets.
+//!
+//! Each type serializes as its `to_bytes` encoding: a lowercase hex string in human-readable
+//! formats such as JSON, and a single byte string in binary formats such as bincode or CBOR.
+//! Deserializing runs the same checks as `from_bytes`.
+
+use crate::text::{from_hex, to_hex};
+use crate::{Ciphertext, PublicKey, SecretKey, SharedSecret, SHARED_SECRET_LEN};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::fmt;
+use zeroize::Zeroizing;
+
+fn serialize_encoded<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&Zeroizing::new(to_hex(bytes)))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn deserialize_encoded<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Zeroizing<Vec<u8>>, D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(EncodedVisitor)
+    } else {
+        deserializer.deserialize_byte_buf(EncodedVisitor)
+    }
+}
+
+/// Accepts a hex string or raw bytes; sequences cover formats that encode bytes as arrays.
+struct EncodedVisitor;
+
+impl<'de> Visitor<'de> for EncodedVisitor {
+    type Value = Zeroizing<Vec<u8>>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a hex string or byte string")
+    }
+
+    fn visit_str<E: de::Error>(self, hex: &str) -> Result<Self::Value, E> {
+        from_hex(hex).map(Zeroizing::new).map_err(|_| E::custom("invalid hex string"))
+    }
+
+    fn visit_bytes<E: de::Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        Ok(Zeroizing::new(bytes.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Zeroizing::new(bytes))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Zeroizing::new(Vec::with_capacity(seq.size_hint().unwrap_or(0)));
+        while let Some(byte) = seq.next_element()? {
+            bytes.push(byte);
+        }
+        Ok(bytes)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_encoded(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PublicKey::from_bytes(&deserialize_encoded(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Writes the secret in the clear, like `SecretKey::to_bytes`; protect the output accordingly.
+impl Serialize for SecretKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_encoded(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SecretKey::from_bytes(&deserialize_encoded(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for Ciphertext {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_encoded(&self.to_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ciphertext {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ciphertext::from_bytes(&deserialize_encoded(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+/// Writes the secret in the clear; protect the output accordingly.
+impl Serialize for SharedSecret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_encoded(self.as_bytes(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SharedSecret {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = deserialize_encoded(deserializer)?;
+        if bytes.len() != SHARED_SECRET_LEN {
+            return Err(de::Error::invalid_length(bytes.len(), &"a 32-byte shared secret"));
+        }
+        Ok(SharedSecret::new(bytes.to_vec()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decapsulate, encapsulate, keypair};
+
+    #[test]
+    fn test_keypair_json_and_bincode_round_trip() {
+        let (pk, sk) = keypair();
+        let json = serde_json::to_string(&(&pk, &sk)).unwrap();
+        assert!(json.starts_with("[\""), "keys should be hex strings, not number arrays");
+        let (pk2, sk2): (PublicKey, SecretKey) = serde_json::from_str(&json).unwrap();
+        assert_eq!(pk2, pk);
+        assert_eq!(sk2.to_bytes(), sk.to_bytes());
+
+        let (ct, ss) = encapsulate(&pk);
+        let binary = bincode::serialize(&(&ct, &ss)).unwrap();
+        assert!(binary.len() < ct.to_bytes().len() + 64);
+        let (ct2, ss2): (Ciphertext, SharedSecret) = bincode::deserialize(&binary).unwrap();
+        assert_eq!(ct2, ct);
+        assert!(ss2 == ss);
+        assert!(decapsulate(&ct2, &sk2) == ss);
+
+        assert!(serde_json::from_str::<PublicKey>("\"abc\"").is_err());
+        assert!(serde_json::from_str::<SharedSecret>("\"00ff\"").is_err());
+    }
+}
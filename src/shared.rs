@@ -0,0 +1,108 @@
+//! A `TrueRandom` shared between threads behind a mutex.
+
+use crate::{QuantumError, TrueRandom};
+use std::sync::{Arc, Mutex, MutexGuard};
+use zeroize::Zeroize;
+
+/// A `TrueRandom` that many threads can draw from, sharing one entropy pool.
+///
+/// Clones are cheap handles to the same generator, which sits behind a mutex locked for the
+/// length of each draw. `global_rng` is one process-wide `SharedRandom`; create your own to
+/// give a thread pool a generator built with `TrueRandomBuilder` or `with_clock`.
+///
+/// A draw only touches the generator's own state and the OS entropy source while the lock is
+/// held, so it cannot deadlock unless a custom `Clock` draws from the same `SharedRandom`.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::SharedRandom;
+/// let rng = SharedRandom::new();
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let rng = rng.clone();
+///         std::thread::spawn(move || rng.generate(32))
+///     })
+///     .collect();
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap().len(), 32);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SharedRandom {
+    inner: Arc<Mutex<TrueRandom>>, // The generator every clone draws from
+}
+
+impl SharedRandom {
+    /// Creates a shared generator seeded from the system, as `TrueRandom::new` does.
+    pub fn new() -> Self {
+        SharedRandom::from(TrueRandom::new())
+    }
+
+    /// Generates `len` random bytes.
+    ///
+    /// # Panics
+    /// If the entropy source fails; see `try_generate`.
+    pub fn generate(&self, len: usize) -> Vec<u8> {
+        self.try_generate(len).expect("random generation failed")
+    }
+
+    /// Generates `len` random bytes, reporting entropy failures instead of panicking.
+    ///
+    /// # Returns
+    /// The random bytes, or the error from `TrueRandom::try_generate`.
+    pub fn try_generate(&self, len: usize) -> Result<Vec<u8>, QuantumError> {
+        self.lock().try_generate(len)
+    }
+
+    /// Fills `dest` with random bytes.
+    pub fn fill(&self, dest: &mut [u8]) {
+        let mut bytes = self.generate(dest.len());
+        dest.copy_from_slice(&bytes);
+        bytes.zeroize();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, TrueRandom> {
+        // A panic while holding the lock cannot leave the pool in a weaker state.
+        self.inner.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for SharedRandom {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<TrueRandom> for SharedRandom {
+    /// Shares an existing generator, keeping the entropy it has already accumulated.
+    fn from(trng: TrueRandom) -> Self {
+        SharedRandom {
+            inner: Arc::new(Mutex::new(trng)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::thread;
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_shared_random_across_threads() {
+        assert_send_sync::<SharedRandom>();
+
+        let rng = SharedRandom::new();
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let rng = rng.clone();
+                thread::spawn(move || rng.generate(32))
+            })
+            .collect();
+        let outputs: HashSet<Vec<u8>> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(outputs.len(), 8, "every thread must get distinct bytes");
+        assert!(outputs.iter().all(|out| out.len() == 32));
+    }
+}
@@ -0,0 +1,121 @@
+//! Synthetic-IV authenticated encryption, for callers who cannot guarantee unique nonces.
+//!
+//! The IV is a keyed SHA3-256 over the associated data and plaintext, and doubles as the tag;
+//! the plaintext is then encrypted with the keystream for that IV. Repeating the same inputs
+//! repeats the ciphertext, revealing only that the messages were equal.
+
+use alloc::vec::Vec;
+use crate::aead::{absorb_framed, apply_keystream, keystream};
+use crate::{ct_eq, QuantumError};
+use sha3::digest::Update;
+use sha3::{Digest, Sha3_256};
+use zeroize::Zeroize;
+
+/// Length of the synthetic IV that prefixes every sealed message.
+const SIV_LEN: usize = 32;
+
+/// Computes the synthetic IV for `plaintext` under `key`.
+fn synthetic_iv(key: &[u8; 32], aad: &[u8], plaintext: &[u8]) -> [u8; SIV_LEN] {
+    let mut mac = Sha3_256::new();
+    Update::update(&mut mac, b"ruption-siv-mac-v1");
+    absorb_framed(&mut mac, key);
+    absorb_framed(&mut mac, aad);
+    absorb_framed(&mut mac, plaintext);
+    mac.finalize().into()
+}
+
+/// Derives the encryption subkey, kept separate from the MAC key.
+fn encryption_key(key: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha3_256::new();
+    Update::update(&mut hasher, b"ruption-siv-enc-v1");
+    Update::update(&mut hasher, key);
+    hasher.finalize().into()
+}
+
+/// Encrypts with nonce-misuse resistance: no nonce is needed at all.
+///
+/// Put a nonce or counter in `aad` to hide repeated messages; without one, sealing the same
+/// message twice gives the same output.
+///
+/// # Arguments
+/// * `key` - A 256-bit key, e.g. from `derive_keys`.
+/// * `aad` - Associated data that is authenticated but not encrypted.
+/// * `plaintext` - The message to encrypt.
+///
+/// # Returns
+/// The 32-byte synthetic IV followed by the ciphertext.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{seal_siv, open_siv};
+/// let key = [5u8; 32];
+/// let sealed = seal_siv(&key, b"header", b"secret");
+/// assert_eq!(open_siv(&key, b"header", &sealed).unwrap(), b"secret");
+/// ```
+pub fn seal_siv(key: &[u8; 32], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let iv = synthetic_iv(key, aad, plaintext);
+    let mut enc_key = encryption_key(key);
+    let mut out = iv.to_vec();
+    out.extend_from_slice(plaintext);
+    apply_keystream(&mut keystream(&enc_key, &iv), &mut out[SIV_LEN..]);
+    enc_key.zeroize();
+    out
+}
+
+/// Decrypts and verifies a message sealed by `seal_siv`.
+///
+/// # Returns
+/// The plaintext, `QuantumError::AuthenticationFailed` if anything was modified (no plaintext
+/// is released), or `QuantumError::InvalidLength` if `sealed` is shorter than the IV.
+pub fn open_siv(key: &[u8; 32], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, QuantumError> {
+    if sealed.len() < SIV_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    let (iv, ciphertext) = sealed.split_at(SIV_LEN);
+    let mut enc_key = encryption_key(key);
+    let mut out = ciphertext.to_vec();
+    apply_keystream(&mut keystream(&enc_key, iv), &mut out);
+    enc_key.zeroize();
+    if !ct_eq(&synthetic_iv(key, aad, &out), iv) {
+        out.zeroize();
+        return Err(QuantumError::AuthenticationFailed);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_siv_reuse_is_deterministic_not_catastrophic() {
+        let key = [1u8; 32];
+        let a = seal_siv(&key, b"aad", b"same message");
+        assert_eq!(a, seal_siv(&key, b"aad", b"same message"));
+        assert_eq!(open_siv(&key, b"aad", &a).unwrap(), b"same message");
+
+        // Same-length messages under the same key get unrelated keystreams, so XORing two
+        // ciphertexts does not give the XOR of the plaintexts.
+        let b = seal_siv(&key, b"aad", b"same massage");
+        assert_ne!(a[..SIV_LEN], b[..SIV_LEN]);
+        let xor: Vec<u8> = a[SIV_LEN..].iter().zip(&b[SIV_LEN..]).map(|(x, y)| x ^ y).collect();
+        let plain_xor: Vec<u8> = b"same message".iter().zip(b"same massage").map(|(x, y)| x ^ y).collect();
+        assert_ne!(xor, plain_xor);
+        assert_ne!(a, seal_siv(&key, b"other", b"same message"));
+    }
+
+    #[test]
+    fn test_siv_rejects_tampering() {
+        let key = [1u8; 32];
+        let sealed = seal_siv(&key, b"aad", b"payload");
+        for i in [0, SIV_LEN, sealed.len() - 1] {
+            let mut tampered = sealed.clone();
+            tampered[i] ^= 1;
+            assert_eq!(open_siv(&key, b"aad", &tampered), Err(QuantumError::AuthenticationFailed));
+        }
+        assert!(open_siv(&[2u8; 32], b"aad", &sealed).is_err());
+        assert!(open_siv(&key, b"aaD", &sealed).is_err());
+        assert_eq!(open_siv(&key, b"aad", &sealed[..8]), Err(QuantumError::InvalidLength));
+        assert_eq!(open_siv(&key, b"", &seal_siv(&key, b"", b"")).unwrap(), b"");
+    }
+}
@@ -0,0 +1,207 @@
+//! Keccak duplex AEAD, built directly on Keccak-f[1600].
+//!
+//! The key and nonce are absorbed first, then the associated data. Plaintext is XORed into
+//! the rate (the resulting rate bytes are the ciphertext), and the tag is squeezed after the
+//! last block. Each phase ends with its own domain byte, so phases cannot be confused.
+
+use alloc::vec::Vec;
+use crate::{ct_eq, QuantumError};
+use zeroize::Zeroize;
+
+/// Rate in bytes for a 512-bit capacity, matching SHAKE256.
+const RATE: usize = 136;
+const TAG_LEN: usize = 32;
+
+const DOMAIN_KEY: u8 = 0x01;
+const DOMAIN_AAD: u8 = 0x02;
+const DOMAIN_TEXT: u8 = 0x03;
+
+struct Duplex {
+    state: [u8; 200],
+}
+
+impl Duplex {
+    fn new(key: &[u8; 32], nonce: &[u8; 16]) -> Self {
+        let mut duplex = Duplex { state: [0u8; 200] };
+        let mut init = [0u8; 22 + 32 + 16];
+        init[..22].copy_from_slice(b"ruption-sponge-aead-v1");
+        init[22..54].copy_from_slice(key);
+        init[54..].copy_from_slice(nonce);
+        duplex.absorb(&init, DOMAIN_KEY);
+        init.zeroize();
+        duplex
+    }
+
+    fn permute(&mut self) {
+        let mut lanes = [0u64; 25];
+        for (lane, bytes) in lanes.iter_mut().zip(self.state.chunks_exact(8)) {
+            *lane = u64::from_le_bytes(bytes.try_into().expect("8-byte lane"));
+        }
+        keccak::f1600(&mut lanes);
+        for (bytes, lane) in self.state.chunks_exact_mut(8).zip(lanes.iter()) {
+            bytes.copy_from_slice(&lane.to_le_bytes());
+        }
+        lanes.zeroize();
+    }
+
+    /// Pads the current block with `domain` and pad10*1, then permutes.
+    fn finish_block(&mut self, used: usize, domain: u8) {
+        self.state[used] ^= domain;
+        self.state[RATE - 1] ^= 0x80;
+        self.permute();
+    }
+
+    fn absorb(&mut self, data: &[u8], domain: u8) {
+        let mut blocks = data.chunks_exact(RATE);
+        for block in &mut blocks {
+            self.xor_in(block);
+            self.permute();
+        }
+        let tail = blocks.remainder();
+        self.xor_in(tail);
+        self.finish_block(tail.len(), domain);
+    }
+
+    fn xor_in(&mut self, data: &[u8]) {
+        for (s, d) in self.state.iter_mut().zip(data) {
+            *s ^= d;
+        }
+    }
+
+    fn encrypt(&mut self, data: &mut [u8]) {
+        let mut used = 0;
+        for (i, byte) in data.iter_mut().enumerate() {
+            used = i % RATE;
+            if i > 0 && used == 0 {
+                self.permute();
+            }
+            self.state[used] ^= *byte;
+            *byte = self.state[used];
+            used += 1;
+        }
+        self.finish_text(data.len(), used);
+    }
+
+    fn decrypt(&mut self, data: &mut [u8]) {
+        let mut used = 0;
+        for (i, byte) in data.iter_mut().enumerate() {
+            used = i % RATE;
+            if i > 0 && used == 0 {
+                self.permute();
+            }
+            let ciphertext = *byte;
+            *byte ^= self.state[used];
+            self.state[used] = ciphertext;
+            used += 1;
+        }
+        self.finish_text(data.len(), used);
+    }
+
+    /// Closes the text phase; a completely full last block gets its padding in a fresh block.
+    fn finish_text(&mut self, len: usize, used: usize) {
+        if len > 0 && used == RATE {
+            self.permute();
+            self.finish_block(0, DOMAIN_TEXT);
+        } else {
+            self.finish_block(used, DOMAIN_TEXT);
+        }
+    }
+
+    fn tag(&self) -> [u8; TAG_LEN] {
+        let mut tag = [0u8; TAG_LEN];
+        tag.copy_from_slice(&self.state[..TAG_LEN]);
+        tag
+    }
+}
+
+impl Drop for Duplex {
+    fn drop(&mut self) {
+        self.state.zeroize();
+    }
+}
+
+/// Encrypts and authenticates with the Keccak duplex AEAD.
+///
+/// Each `(key, nonce)` pair must be used for at most one message.
+///
+/// # Arguments
+/// * `key` - A 256-bit secret key, e.g. from `derive_keys`.
+/// * `nonce` - A unique 128-bit nonce.
+/// * `aad` - Associated data that is authenticated but not encrypted.
+/// * `plaintext` - The message to encrypt.
+///
+/// # Returns
+/// The ciphertext followed by a 32-byte tag.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{seal_sponge, open_sponge};
+/// let key = [7u8; 32];
+/// let nonce = [1u8; 16];
+/// let sealed = seal_sponge(&key, &nonce, b"header", b"secret");
+/// assert_eq!(open_sponge(&key, &nonce, b"header", &sealed).unwrap(), b"secret");
+/// ```
+pub fn seal_sponge(key: &[u8; 32], nonce: &[u8; 16], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    let mut duplex = Duplex::new(key, nonce);
+    duplex.absorb(aad, DOMAIN_AAD);
+    let mut out = plaintext.to_vec();
+    duplex.encrypt(&mut out);
+    out.extend_from_slice(&duplex.tag());
+    out
+}
+
+/// Verifies and decrypts a message sealed by `seal_sponge`.
+///
+/// # Returns
+/// The plaintext, or `QuantumError::AuthenticationFailed` if anything was modified;
+/// no plaintext is released on failure.
+pub fn open_sponge(key: &[u8; 32], nonce: &[u8; 16], aad: &[u8], sealed: &[u8]) -> Result<Vec<u8>, QuantumError> {
+    if sealed.len() < TAG_LEN {
+        return Err(QuantumError::InvalidLength);
+    }
+    let (ciphertext, tag) = sealed.split_at(sealed.len() - TAG_LEN);
+    let mut duplex = Duplex::new(key, nonce);
+    duplex.absorb(aad, DOMAIN_AAD);
+    let mut out = ciphertext.to_vec();
+    duplex.decrypt(&mut out);
+    if !ct_eq(&duplex.tag(), tag) {
+        out.zeroize();
+        return Err(QuantumError::AuthenticationFailed);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sponge_round_trip() {
+        let key = [3u8; 32];
+        let nonce = [9u8; 16];
+        for len in [0, 1, RATE - 1, RATE, RATE + 1, 3 * RATE, 1000] {
+            let plaintext: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let sealed = seal_sponge(&key, &nonce, b"aad", &plaintext);
+            assert_eq!(sealed.len(), len + TAG_LEN);
+            assert_eq!(open_sponge(&key, &nonce, b"aad", &sealed).unwrap(), plaintext);
+        }
+        assert_ne!(seal_sponge(&key, &nonce, b"", b"x"), seal_sponge(&key, &[8u8; 16], b"", b"x"));
+    }
+
+    #[test]
+    fn test_sponge_detects_tampering() {
+        let key = [3u8; 32];
+        let nonce = [9u8; 16];
+        let sealed = seal_sponge(&key, &nonce, b"aad", &[0x55; RATE]);
+
+        let mut flipped = sealed.clone();
+        flipped[RATE - 1] ^= 1;
+        assert_eq!(open_sponge(&key, &nonce, b"aad", &flipped), Err(QuantumError::AuthenticationFailed));
+        let mut bad_tag = sealed.clone();
+        bad_tag[RATE] ^= 1;
+        assert!(open_sponge(&key, &nonce, b"aad", &bad_tag).is_err());
+        assert!(open_sponge(&key, &nonce, b"aaD", &sealed).is_err());
+        assert!(open_sponge(&[4u8; 32], &nonce, b"aad", &sealed).is_err());
+        assert!(open_sponge(&key, &nonce, b"aad", &sealed[..RATE]).is_err());
+    }
+}
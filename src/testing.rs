@@ -0,0 +1,405 @@
+//! Deterministic test vectors, session traces and decapsulation diagnostics.
+//!
+//! Everything here is seeded and therefore predictable; never use it to make real keys.
+
+use crate::text::to_hex;
+use crate::{
+    decapsulate, derive_keys, encapsulate_from, keypair_from, Ciphertext, Params, PublicKey, QuantumError, SecretKey,
+};
+use serde::{Deserialize, Serialize};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+use std::fmt;
+use std::path::Path;
+
+/// Number of keys derived from the shared secret in each vector.
+const DERIVED_KEYS: usize = 3;
+
+/// One complete, correlated run of the KEM, with every value hex-encoded.
+///
+/// `Display` writes it in the `name = value` style of NIST KAT files; it also serializes to
+/// the JSON objects read by `verify_test_vectors`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    /// The 32-byte seed the whole run was derived from.
+    pub seed: String,
+    /// Public key: the lattice matrix rows in order, then the code generator.
+    pub public_key: String,
+    /// Secret key: the lattice secret, then the code secret.
+    pub secret_key: String,
+    /// Ciphertext: the lattice component, then the code component.
+    pub ciphertext: String,
+    /// The encapsulated shared secret.
+    pub shared_secret: String,
+    /// The first keys from `derive_keys` on the shared secret.
+    pub derived_keys: Vec<String>,
+}
+
+impl fmt::Display for TestVector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "seed = {}", self.seed)?;
+        writeln!(f, "pk = {}", self.public_key)?;
+        writeln!(f, "sk = {}", self.secret_key)?;
+        writeln!(f, "ct = {}", self.ciphertext)?;
+        writeln!(f, "ss = {}", self.shared_secret)?;
+        for (i, key) in self.derived_keys.iter().enumerate() {
+            writeln!(f, "key{i} = {key}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs keygen, encapsulation, decapsulation and key derivation from a fixed seed.
+///
+/// All randomness comes from SHAKE256 over the seed, so the same seed always yields the same
+/// vector, on any platform.
+///
+/// # Panics
+/// If decapsulation does not recover the encapsulated secret, since the vector would be invalid.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::generate_test_vector;
+/// let vector = generate_test_vector(&[0u8; 32]);
+/// assert_eq!(vector, generate_test_vector(&[0u8; 32]));
+/// assert_eq!(vector.shared_secret.len(), 64);
+/// ```
+pub fn generate_test_vector(seed: &[u8; 32]) -> TestVector {
+    let mut draw = seeded_drbg(seed);
+    let (pk, sk) = keypair_from(&Params::toy(), &mut draw);
+    let (ct, ss) = encapsulate_from(&pk, &mut draw);
+    assert_eq!(decapsulate(&ct, &sk).as_bytes(), ss.as_bytes(), "decapsulation mismatch");
+
+    TestVector {
+        seed: to_hex(seed),
+        public_key: public_key_hex(&pk),
+        secret_key: secret_key_hex(&sk),
+        ciphertext: to_hex(&[&ct.lattice_cipher[..], &ct.code_cipher[..]].concat()),
+        shared_secret: to_hex(ss.as_bytes()),
+        derived_keys: derive_keys(&ss, DERIVED_KEYS).iter().map(|k| to_hex(k)).collect(),
+    }
+}
+
+/// Everything needed to reproduce one two-party session, hex-encoded.
+///
+/// Attach a trace to a bug report: it serializes to JSON like `TestVector`, and `replay`
+/// reruns a deserialized copy and checks every value.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::SessionTrace;
+/// let trace = SessionTrace::capture(&[9u8; 32]);
+/// assert_eq!(trace.initiator_secret, trace.responder_secret);
+/// trace.replay();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionTrace {
+    /// The 32-byte seed all randomness in the session was drawn from.
+    pub seed: String,
+    /// The initiator's public key.
+    pub initiator_public_key: String,
+    /// The initiator's secret key.
+    pub initiator_secret_key: String,
+    /// The responder's public key, which the initiator encapsulates to.
+    pub responder_public_key: String,
+    /// The responder's secret key.
+    pub responder_secret_key: String,
+    /// The ciphertext sent from initiator to responder, in the `Ciphertext::to_hex` form.
+    pub ciphertext: String,
+    /// The secret the initiator obtained from encapsulation.
+    pub initiator_secret: String,
+    /// The secret the responder obtained from decapsulation.
+    pub responder_secret: String,
+}
+
+impl SessionTrace {
+    /// Runs a seeded session and records every intermediate value.
+    ///
+    /// Both parties generate keypairs, then the initiator encapsulates to the responder,
+    /// who decapsulates. The two secrets are recorded as-is, so a mismatch shows up in the
+    /// trace instead of aborting the capture.
+    pub fn capture(seed: &[u8; 32]) -> Self {
+        let mut draw = seeded_drbg(seed);
+        let (initiator_pk, initiator_sk) = keypair_from(&Params::toy(), &mut draw);
+        let (responder_pk, responder_sk) = keypair_from(&Params::toy(), &mut draw);
+        let (ct, initiator_secret) = encapsulate_from(&responder_pk, &mut draw);
+        let responder_secret = decapsulate(&ct, &responder_sk);
+
+        SessionTrace {
+            seed: to_hex(seed),
+            initiator_public_key: public_key_hex(&initiator_pk),
+            initiator_secret_key: secret_key_hex(&initiator_sk),
+            responder_public_key: public_key_hex(&responder_pk),
+            responder_secret_key: secret_key_hex(&responder_sk),
+            ciphertext: ct.to_hex(),
+            initiator_secret: to_hex(initiator_secret.as_bytes()),
+            responder_secret: to_hex(responder_secret.as_bytes()),
+        }
+    }
+
+    /// Reruns the session from the recorded seed and checks every recorded value.
+    ///
+    /// # Panics
+    /// If the seed is not 64 hex digits, or naming the first value that came out differently.
+    pub fn replay(&self) {
+        let again = SessionTrace::capture(&try_parse_seed(&self.seed).expect("seed must be 64 hex digits"));
+        assert_eq!(self.initiator_public_key, again.initiator_public_key, "initiator public key differs");
+        assert_eq!(self.initiator_secret_key, again.initiator_secret_key, "initiator secret key differs");
+        assert_eq!(self.responder_public_key, again.responder_public_key, "responder public key differs");
+        assert_eq!(self.responder_secret_key, again.responder_secret_key, "responder secret key differs");
+        assert_eq!(self.ciphertext, again.ciphertext, "ciphertext differs");
+        assert_eq!(self.initiator_secret, again.initiator_secret, "initiator secret differs");
+        assert_eq!(self.responder_secret, again.responder_secret, "responder secret differs");
+    }
+}
+
+/// What `decapsulate_debug` observed while decapsulating one ciphertext.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecapsDiagnostics {
+    /// Length of the ciphertext's lattice component.
+    pub lattice_len: usize,
+    /// Length of the ciphertext's code component.
+    pub code_len: usize,
+    /// Whether both component lengths match the secret key's dimensions.
+    pub dimensions_match: bool,
+    /// Hamming weight of the error vector recovered by code decoding.
+    ///
+    /// Always `None` for now: the code-based component is a placeholder and decodes no error vector.
+    pub error_weight: Option<u32>,
+    /// Whether decoding produced a usable secret.
+    pub decoding_succeeded: bool,
+    /// The recovered shared secret, hex-encoded, for comparison with encapsulation.
+    pub shared_secret: String,
+}
+
+/// Decapsulates like `decapsulate`, but reports intermediate values for debugging.
+///
+/// Meant for tracking down decapsulation failures while developing the lattice and code
+/// decoders; the report contains the recovered secret, so never log it in production.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{keypair, encapsulate, decapsulate_debug};
+/// let (pk, sk) = keypair();
+/// let (ct, _ss) = encapsulate(&pk);
+/// assert!(decapsulate_debug(&ct, &sk).decoding_succeeded);
+/// ```
+pub fn decapsulate_debug(ct: &Ciphertext, sk: &SecretKey) -> DecapsDiagnostics {
+    let dimensions_match = ct.params() == sk.params();
+    let secret = decapsulate(ct, sk);
+    DecapsDiagnostics {
+        lattice_len: ct.lattice_cipher.len(),
+        code_len: ct.code_cipher.len(),
+        dimensions_match,
+        error_weight: None,
+        decoding_succeeded: dimensions_match,
+        shared_secret: to_hex(secret.as_bytes()),
+    }
+}
+
+/// One entry of a vector file: a seed plus whichever expected values the file records.
+///
+/// Public keys are large, so files may leave out any field except the seed.
+#[derive(Deserialize)]
+struct ExpectedVector {
+    seed: String,
+    public_key: Option<String>,
+    secret_key: Option<String>,
+    ciphertext: Option<String>,
+    shared_secret: Option<String>,
+    derived_keys: Option<Vec<String>>,
+}
+
+/// The outcome of checking one vector from a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorOutcome {
+    /// The vector's seed, as hex.
+    pub seed: String,
+    /// Names of the fields that did not match, empty if the vector passed.
+    pub mismatches: Vec<&'static str>,
+}
+
+impl VectorOutcome {
+    /// Returns `true` if every recorded field was reproduced.
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Per-vector results of `verify_test_vectors`, in file order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationReport {
+    /// One outcome per vector in the file.
+    pub outcomes: Vec<VectorOutcome>,
+}
+
+impl VerificationReport {
+    /// Returns `true` if the file held at least one vector and all of them passed.
+    pub fn all_passed(&self) -> bool {
+        !self.outcomes.is_empty() && self.outcomes.iter().all(VectorOutcome::passed)
+    }
+}
+
+/// Checks that this build reproduces every vector in a JSON file.
+///
+/// The file holds an array of objects with the fields of `TestVector`; `seed` is required and
+/// the others are compared only if present.
+///
+/// # Returns
+/// A `VerificationReport`, `QuantumError::Io` if the file cannot be read, or
+/// `QuantumError::Deserialize` if it is not a valid vector file.
+pub fn verify_test_vectors(path: &Path) -> Result<VerificationReport, QuantumError> {
+    let contents = std::fs::read(path).map_err(|err| QuantumError::Io(err.kind()))?;
+    let expected: Vec<ExpectedVector> = serde_json::from_slice(&contents).map_err(|_| QuantumError::Deserialize)?;
+
+    let mut outcomes = Vec::with_capacity(expected.len());
+    for vector in expected {
+        let actual = generate_test_vector(&try_parse_seed(&vector.seed).ok_or(QuantumError::Deserialize)?);
+        let checks = [
+            ("public_key", vector.public_key.map(|v| v == actual.public_key)),
+            ("secret_key", vector.secret_key.map(|v| v == actual.secret_key)),
+            ("ciphertext", vector.ciphertext.map(|v| v == actual.ciphertext)),
+            ("shared_secret", vector.shared_secret.map(|v| v == actual.shared_secret)),
+            ("derived_keys", vector.derived_keys.map(|v| v == actual.derived_keys)),
+        ];
+        let mismatches = checks.iter().filter(|(_, ok)| *ok == Some(false)).map(|(name, _)| *name).collect();
+        outcomes.push(VectorOutcome {
+            seed: actual.seed,
+            mismatches,
+        });
+    }
+    Ok(VerificationReport { outcomes })
+}
+
+/// Returns a deterministic byte source: SHAKE256 over a label and the seed.
+fn seeded_drbg(seed: &[u8; 32]) -> impl FnMut(usize) -> Vec<u8> {
+    let mut xof = Shake256::default();
+    xof.update(b"ruption-test-drbg-v1");
+    xof.update(seed);
+    let mut reader = xof.finalize_xof();
+    move |len| {
+        let mut out = vec![0u8; len];
+        reader.read(&mut out);
+        out
+    }
+}
+
+/// Public key bytes: the lattice matrix rows in order, then the code generator.
+fn public_key_hex(pk: &PublicKey) -> String {
+    let mut bytes = pk._lattice_matrix.concat();
+    bytes.extend_from_slice(&pk._code_generator);
+    to_hex(&bytes)
+}
+
+/// Secret key bytes: the lattice secret, then the code secret.
+fn secret_key_hex(sk: &SecretKey) -> String {
+    to_hex(&[&sk._lattice_secret[..], &sk._code_secret[..]].concat())
+}
+
+fn try_parse_seed(hex: &str) -> Option<[u8; 32]> {
+    if hex.len() != 64 || !hex.is_ascii() {
+        return None;
+    }
+    let mut seed = [0u8; 32];
+    for (byte, pair) in seed.iter_mut().zip(hex.as_bytes().chunks(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_vector() {
+        let seed = [0x42u8; 32];
+        let first = generate_test_vector(&seed);
+        let second = generate_test_vector(&seed);
+        assert_eq!(first, second);
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(first.derived_keys.len(), DERIVED_KEYS);
+        let toy = Params::toy();
+        assert_eq!(first.ciphertext.len(), 2 * (crate::lwe::cipher_len(toy.lattice_dim()) + toy.code_length()));
+
+        let other = generate_test_vector(&[0x43u8; 32]);
+        assert_ne!(first.secret_key, other.secret_key);
+        assert_ne!(first.shared_secret, other.shared_secret);
+    }
+
+    #[test]
+    fn test_session_trace_replays() {
+        let trace = SessionTrace::capture(&[0x17u8; 32]);
+        assert_eq!(trace.initiator_secret, trace.responder_secret);
+        assert_ne!(trace.initiator_secret_key, trace.responder_secret_key);
+        assert_eq!(trace, SessionTrace::capture(&[0x17u8; 32]));
+        trace.replay();
+
+        let mut corrupted = trace.clone();
+        corrupted.ciphertext.replace_range(..2, "zz");
+        assert!(std::panic::catch_unwind(|| corrupted.replay()).is_err());
+    }
+
+    #[test]
+    fn test_session_trace_json_round_trip() {
+        let trace = SessionTrace::capture(&[0x29u8; 32]);
+        let json = serde_json::to_string(&trace).unwrap();
+        let loaded: SessionTrace = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, trace);
+        loaded.replay();
+
+        let ct = Ciphertext::from_hex(&loaded.ciphertext).unwrap();
+        assert_eq!(ct.to_hex(), trace.ciphertext, "the trace records the wire form");
+    }
+
+    #[test]
+    fn test_decapsulate_debug_reports_success() {
+        let (pk, sk) = crate::keypair();
+        let (ct, ss) = crate::encapsulate(&pk);
+        let report = decapsulate_debug(&ct, &sk);
+        assert!(report.dimensions_match);
+        assert!(report.decoding_succeeded);
+        assert_eq!(report.shared_secret, to_hex(ss.as_bytes()));
+        let expected = (crate::lwe::cipher_len(crate::LATTICE_DIM), crate::CODE_LENGTH);
+        assert_eq!((report.lattice_len, report.code_len), expected);
+
+        let (big_pk, _) = keypair_from(&Params::new(8, 16).unwrap(), |len| vec![1u8; len]);
+        let (mismatched, _) = crate::encapsulate(&big_pk);
+        assert!(!decapsulate_debug(&mismatched, &sk).decoding_succeeded);
+    }
+
+    #[test]
+    #[ignore = "regenerates tests/vectors/kat.json"]
+    fn write_bundled_vectors() {
+        let vectors: Vec<serde_json::Value> = [[0u8; 32], [0x42u8; 32]]
+            .iter()
+            .map(|seed| {
+                let mut value = serde_json::to_value(generate_test_vector(seed)).unwrap();
+                value.as_object_mut().unwrap().remove("public_key");
+                value
+            })
+            .collect();
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/kat.json");
+        std::fs::write(path, serde_json::to_string_pretty(&vectors).unwrap() + "\n").unwrap();
+    }
+
+    #[test]
+    fn test_bundled_vectors_verify() {
+        let bundled = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/vectors/kat.json");
+        let report = verify_test_vectors(&bundled).unwrap();
+        assert_eq!(report.outcomes.len(), 2);
+        assert!(report.all_passed(), "{report:?}");
+
+        let mut vector = serde_json::to_value(generate_test_vector(&[1u8; 32])).unwrap();
+        vector["shared_secret"] = serde_json::Value::from("00".repeat(32));
+        let corrupted = std::env::temp_dir().join(format!("ruption-kat-{}.json", std::process::id()));
+        std::fs::write(&corrupted, serde_json::to_string(&[vector]).unwrap()).unwrap();
+        let report = verify_test_vectors(&corrupted).unwrap();
+        std::fs::remove_file(&corrupted).unwrap();
+        assert_eq!(report.outcomes[0].mismatches, ["shared_secret"]);
+        assert!(!report.all_passed());
+
+        let missing = verify_test_vectors(Path::new("/nonexistent/kat.json"));
+        assert_eq!(missing.err(), Some(QuantumError::Io(std::io::ErrorKind::NotFound)));
+    }
+}
@@ -0,0 +1,218 @@
+//! Hex and base64 text forms of keys and ciphertexts, for config files and URLs.
+//!
+//! Both encode the `to_bytes` form, and decoding runs the same checks as `from_bytes`.
+//! Base64 uses the standard alphabet with `=` padding (RFC 4648, section 4); percent-encode
+//! `+`, `/` and `=` before putting it in a URL. Decoders accept only canonical input.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use crate::{Ciphertext, PublicKey, QuantumError, SecretKey};
+use zeroize::Zeroizing;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encodes bytes as lowercase hex.
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        out.push(HEX_DIGITS[(b >> 4) as usize] as char);
+        out.push(HEX_DIGITS[(b & 0x0F) as usize] as char);
+    }
+    out
+}
+
+/// Decodes lowercase or uppercase hex.
+///
+/// Returns `QuantumError::Deserialize` for an odd length or a non-hex character.
+pub(crate) fn from_hex(hex: &str) -> Result<Vec<u8>, QuantumError> {
+    if !hex.len().is_multiple_of(2) {
+        return Err(QuantumError::Deserialize);
+    }
+    let digit = |c: u8| (c as char).to_digit(16).map(|d| d as u8).ok_or(QuantumError::Deserialize);
+    hex.as_bytes().chunks_exact(2).map(|pair| Ok(digit(pair[0])? << 4 | digit(pair[1])?)).collect()
+}
+
+/// Encodes bytes as padded standard base64.
+pub(crate) fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let mut group = [0u8; 3];
+        group[..chunk.len()].copy_from_slice(chunk);
+        let bits = u32::from_be_bytes([0, group[0], group[1], group[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64_ALPHABET[(bits >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Decodes padded standard base64.
+///
+/// Returns `QuantumError::Deserialize` for a length that is not a multiple of 4, a character
+/// outside the alphabet, misplaced padding, or nonzero bits after the last byte.
+pub(crate) fn from_base64(text: &str) -> Result<Vec<u8>, QuantumError> {
+    let bytes = text.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return Err(QuantumError::Deserialize);
+    }
+    let padding = bytes.iter().rev().take_while(|&&b| b == b'=').count();
+    if padding > 2 {
+        return Err(QuantumError::Deserialize);
+    }
+    let value = |c: u8| BASE64_ALPHABET.iter().position(|&a| a == c).ok_or(QuantumError::Deserialize);
+
+    let groups = bytes.len() / 4;
+    let mut out = Vec::with_capacity(groups * 3);
+    for (index, group) in bytes.chunks_exact(4).enumerate() {
+        let digits = if index + 1 == groups { 4 - padding } else { 4 };
+        let mut bits = 0u32;
+        for (i, &c) in group.iter().enumerate() {
+            let v = if i < digits { value(c)? as u32 } else { 0 };
+            bits = bits << 6 | v;
+        }
+        let decoded = bits.to_be_bytes();
+        let len = digits - 1;
+        if decoded[1 + len..].iter().any(|&b| b != 0) {
+            return Err(QuantumError::Deserialize);
+        }
+        out.extend_from_slice(&decoded[1..1 + len]);
+    }
+    Ok(out)
+}
+
+impl PublicKey {
+    /// Encodes the key as lowercase hex.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, PublicKey};
+    /// let (pk, _sk) = keypair();
+    /// assert_eq!(PublicKey::from_hex(&pk.to_hex()).unwrap(), pk);
+    /// assert!(PublicKey::from_hex("abc").is_err());
+    /// ```
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.to_bytes())
+    }
+
+    /// Decodes a key from `to_hex`.
+    ///
+    /// # Returns
+    /// The `PublicKey`, `QuantumError::Deserialize` if the text is not valid hex or the
+    /// decoded bytes are malformed, or `QuantumError::InvalidLength` as for `from_bytes`.
+    pub fn from_hex(hex: &str) -> Result<Self, QuantumError> {
+        PublicKey::from_bytes(&from_hex(hex)?)
+    }
+
+    /// Encodes the key as padded standard base64.
+    ///
+    /// # Examples
+    /// ```
+    /// use ruption_quantum_encrypt::{keypair, PublicKey};
+    /// let (pk, _sk) = keypair();
+    /// assert_eq!(PublicKey::from_base64(&pk.to_base64()).unwrap(), pk);
+    /// ```
+    pub fn to_base64(&self) -> String {
+        to_base64(&self.to_bytes())
+    }
+
+    /// Decodes a key from `to_base64`, with the errors of `from_hex`.
+    pub fn from_base64(text: &str) -> Result<Self, QuantumError> {
+        PublicKey::from_bytes(&from_base64(text)?)
+    }
+}
+
+impl SecretKey {
+    /// Encodes the secret key as lowercase hex, in a buffer that is zeroized on drop.
+    ///
+    /// Like `to_bytes`, the result is the secret in the clear.
+    pub fn to_hex(&self) -> Zeroizing<String> {
+        Zeroizing::new(to_hex(&self.to_bytes()))
+    }
+
+    /// Decodes a secret key from `to_hex`, with the errors of `PublicKey::from_hex`.
+    pub fn from_hex(hex: &str) -> Result<Self, QuantumError> {
+        SecretKey::from_bytes(&Zeroizing::new(from_hex(hex)?))
+    }
+
+    /// Encodes the secret key as padded standard base64, zeroized on drop.
+    pub fn to_base64(&self) -> Zeroizing<String> {
+        Zeroizing::new(to_base64(&self.to_bytes()))
+    }
+
+    /// Decodes a secret key from `to_base64`, with the errors of `PublicKey::from_hex`.
+    pub fn from_base64(text: &str) -> Result<Self, QuantumError> {
+        SecretKey::from_bytes(&Zeroizing::new(from_base64(text)?))
+    }
+}
+
+impl Ciphertext {
+    /// Encodes the ciphertext as lowercase hex.
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.to_bytes())
+    }
+
+    /// Decodes a ciphertext from `to_hex`, with the errors of `PublicKey::from_hex`.
+    pub fn from_hex(hex: &str) -> Result<Self, QuantumError> {
+        Ciphertext::from_bytes(&from_hex(hex)?)
+    }
+
+    /// Encodes the ciphertext as padded standard base64.
+    pub fn to_base64(&self) -> String {
+        to_base64(&self.to_bytes())
+    }
+
+    /// Decodes a ciphertext from `to_base64`, with the errors of `PublicKey::from_hex`.
+    pub fn from_base64(text: &str) -> Result<Self, QuantumError> {
+        Ciphertext::from_bytes(&from_base64(text)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use crate::{decapsulate, encapsulate, keypair};
+
+    #[test]
+    fn test_base64_matches_rfc4648_vectors() {
+        let vectors = [("", ""), ("f", "Zg=="), ("fo", "Zm8="), ("foo", "Zm9v"), ("foobar", "Zm9vYmFy")];
+        for (plain, encoded) in vectors {
+            assert_eq!(to_base64(plain.as_bytes()), encoded);
+            assert_eq!(from_base64(encoded).unwrap(), plain.as_bytes());
+        }
+        for bad in ["Zg=", "Z===", "Zg=a", "Zh==", "Zm9v!A==", "=Zm9"] {
+            assert_eq!(from_base64(bad), Err(QuantumError::Deserialize), "{bad}");
+        }
+        assert_eq!(from_hex("00Ff").unwrap(), [0x00, 0xff]);
+        assert_eq!(from_hex("0"), Err(QuantumError::Deserialize));
+        assert_eq!(from_hex("zz"), Err(QuantumError::Deserialize));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_key_and_ciphertext_text_round_trips() {
+        let (pk, sk) = keypair();
+        let (ct, ss) = encapsulate(&pk);
+        assert_eq!(PublicKey::from_hex(&pk.to_hex()).unwrap(), pk);
+        assert_eq!(PublicKey::from_base64(&pk.to_base64()).unwrap(), pk);
+        assert_eq!(Ciphertext::from_hex(&ct.to_hex()).unwrap(), ct);
+        assert_eq!(Ciphertext::from_base64(&ct.to_base64()).unwrap(), ct);
+        for decoded in [SecretKey::from_hex(&sk.to_hex()), SecretKey::from_base64(&sk.to_base64())] {
+            let decoded = decoded.unwrap();
+            assert_eq!(decoded.to_bytes(), sk.to_bytes());
+            assert!(decapsulate(&ct, &decoded) == ss);
+        }
+
+        let mut odd = ct.to_hex();
+        odd.pop();
+        assert_eq!(Ciphertext::from_hex(&odd), Err(QuantumError::Deserialize));
+        let mut bad = pk.to_base64();
+        bad.replace_range(0..1, "*");
+        assert_eq!(PublicKey::from_base64(&bad), Err(QuantumError::Deserialize));
+    }
+}
@@ -0,0 +1,230 @@
+//! One-stop configuration for `TrueRandom`.
+//!
+//! Every setting has the same default as `TrueRandom::new`, so a builder with nothing set
+//! builds an equivalent generator.
+
+use crate::{health, Clock, SystemClock, TrueRandom, JITTER_ROUNDS};
+use std::fmt;
+
+/// An optional entropy source mixed into the `TrueRandom` pool on every draw.
+///
+/// OS entropy is not listed: it seeds every generator and cannot be turned off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntropySource {
+    /// Timing jitter from short sleeps.
+    Jitter,
+    /// The timing-based "quantum-inspired" simulation.
+    QuantumSim,
+    /// The CPU's RDSEED instruction; needs the `x86-hwrng` feature and a capable CPU.
+    HardwareRng,
+}
+
+impl EntropySource {
+    /// The sources a generator uses unless told otherwise.
+    pub(crate) const DEFAULT: [EntropySource; 3] =
+        [EntropySource::Jitter, EntropySource::QuantumSim, EntropySource::HardwareRng];
+
+    /// Returns `true` if this source can produce output in this build and on this machine.
+    pub fn is_available(self) -> bool {
+        match self {
+            EntropySource::Jitter | EntropySource::QuantumSim => true,
+            EntropySource::HardwareRng => hardware_rng_available(),
+        }
+    }
+}
+
+#[cfg(all(feature = "x86-hwrng", any(target_arch = "x86", target_arch = "x86_64")))]
+fn hardware_rng_available() -> bool {
+    crate::hwrng::rdseed_bytes(1).is_some()
+}
+
+#[cfg(not(all(feature = "x86-hwrng", any(target_arch = "x86", target_arch = "x86_64"))))]
+fn hardware_rng_available() -> bool {
+    false
+}
+
+/// Errors from `TrueRandomBuilder::build`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RngError {
+    /// A source is required but unavailable in this build or on this machine.
+    SourceUnavailable(EntropySource),
+    /// Two settings contradict each other, or one is out of range.
+    InvalidSetting(&'static str),
+    /// The OS entropy source could not seed the generator.
+    EntropyUnavailable,
+}
+
+impl fmt::Display for RngError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RngError::SourceUnavailable(source) => write!(f, "entropy source {source:?} is unavailable"),
+            RngError::InvalidSetting(reason) => write!(f, "invalid generator setting: {reason}"),
+            RngError::EntropyUnavailable => write!(f, "system entropy unavailable"),
+        }
+    }
+}
+
+impl std::error::Error for RngError {}
+
+/// Configures a `TrueRandom` before its first use.
+///
+/// # Examples
+/// ```
+/// use ruption_quantum_encrypt::{EntropySource, TrueRandom};
+/// let mut trng = TrueRandom::builder()
+///     .jitter_rounds(32)
+///     .reseed_interval(1024)
+///     .source(EntropySource::Jitter)
+///     .continuous_health_check(true)
+///     .build()
+///     .unwrap();
+/// assert_eq!(trng.generate(32).len(), 32);
+/// ```
+pub struct TrueRandomBuilder {
+    clock: Box<dyn Clock + Send>,  // Time source for jitter and simulated entropy
+    jitter_rounds: usize,          // Jitter samples collected per draw
+    reseed_interval: Option<u64>,  // Draws between automatic reseeds, if any
+    sources: Vec<EntropySource>,   // Optional sources to use; empty means the defaults
+    continuous_health_check: bool, // Whether to run the SP 800-90B continuous tests
+    require_sources: bool,         // Whether an unavailable source fails the build
+}
+
+impl TrueRandomBuilder {
+    /// Starts from the settings of `TrueRandom::new`.
+    pub fn new() -> Self {
+        TrueRandomBuilder {
+            clock: Box::new(SystemClock),
+            jitter_rounds: JITTER_ROUNDS,
+            reseed_interval: None,
+            sources: Vec::new(),
+            continuous_health_check: false,
+            require_sources: false,
+        }
+    }
+
+    /// Reads time from `clock` instead of the system clock; see `TrueRandom::with_clock`.
+    pub fn clock<C: Clock + Send + 'static>(mut self, clock: C) -> Self {
+        self.clock = Box::new(clock);
+        self
+    }
+
+    /// Sets how many jitter samples each draw collects (default 10).
+    pub fn jitter_rounds(mut self, rounds: usize) -> Self {
+        self.jitter_rounds = rounds;
+        self
+    }
+
+    /// Condenses the pool and reseeds from the OS after every `draws` draws (default: never).
+    pub fn reseed_interval(mut self, draws: u64) -> Self {
+        self.reseed_interval = Some(draws);
+        self
+    }
+
+    /// Adds `source` to the optional sources used.
+    ///
+    /// Once any source is added, only the added ones are used; otherwise all are, as in
+    /// `TrueRandom::new`.
+    pub fn source(mut self, source: EntropySource) -> Self {
+        if !self.sources.contains(&source) {
+            self.sources.push(source);
+        }
+        self
+    }
+
+    /// Turns the continuous health tests on or off; see
+    /// `TrueRandom::with_continuous_health_check`.
+    pub fn continuous_health_check(mut self, enabled: bool) -> Self {
+        self.continuous_health_check = enabled;
+        self
+    }
+
+    /// Makes `build` fail if a selected source is unavailable, instead of skipping it.
+    pub fn require_sources(mut self, required: bool) -> Self {
+        self.require_sources = required;
+        self
+    }
+
+    /// Builds the generator and seeds it from the OS.
+    ///
+    /// # Returns
+    /// The configured `TrueRandom`; `RngError::SourceUnavailable` if sources are required
+    /// and one is missing; or `RngError::InvalidSetting` for a zero reseed interval, or health
+    /// checks without any jitter samples to check; or `RngError::EntropyUnavailable` if OS
+    /// seeding fails.
+    pub fn build(self) -> Result<TrueRandom, RngError> {
+        let sources = if self.sources.is_empty() {
+            EntropySource::DEFAULT.to_vec()
+        } else {
+            self.sources
+        };
+        if self.require_sources {
+            if let Some(&missing) = sources.iter().find(|source| !source.is_available()) {
+                return Err(RngError::SourceUnavailable(missing));
+            }
+        }
+        if self.reseed_interval == Some(0) {
+            return Err(RngError::InvalidSetting("reseed interval must be non-zero"));
+        }
+        if self.continuous_health_check && (self.jitter_rounds == 0 || !sources.contains(&EntropySource::Jitter)) {
+            return Err(RngError::InvalidSetting("continuous health check needs jitter samples"));
+        }
+
+        let mut trng = TrueRandom::try_with_boxed_clock(self.clock).map_err(|_| RngError::EntropyUnavailable)?;
+        trng.jitter_rounds = self.jitter_rounds;
+        trng.reseed_interval = self.reseed_interval;
+        trng.sources = sources;
+        trng.health = self.continuous_health_check.then(health::ContinuousHealth::default);
+        Ok(trng)
+    }
+}
+
+impl Default for TrueRandomBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_settings_take_effect() {
+        let mut trng = TrueRandom::builder()
+            .jitter_rounds(4)
+            .reseed_interval(3)
+            .source(EntropySource::Jitter)
+            .continuous_health_check(true)
+            .build()
+            .unwrap();
+        assert!(trng.health.is_some());
+
+        // Only the 4 jitter samples join the 64-byte OS seed.
+        trng.generate(32);
+        assert_eq!(trng.entropy_pool.len(), 64 + 4);
+        trng.generate(32);
+        // The third draw reseeds: a 32-byte digest plus a fresh 64-byte OS seed.
+        trng.generate(32);
+        assert_eq!(trng.entropy_pool.len(), 32 + 64);
+
+        let default = TrueRandom::builder().build().unwrap();
+        assert_eq!(default.jitter_rounds, JITTER_ROUNDS);
+        assert_eq!(default.sources, EntropySource::DEFAULT);
+        assert!(default.health.is_none());
+    }
+
+    #[test]
+    fn test_builder_rejects_bad_settings() {
+        let invalid = |builder: TrueRandomBuilder| builder.build().err();
+        assert!(matches!(invalid(TrueRandom::builder().reseed_interval(0)), Some(RngError::InvalidSetting(_))));
+        let no_jitter = TrueRandom::builder().source(EntropySource::QuantumSim).continuous_health_check(true);
+        assert!(matches!(invalid(no_jitter), Some(RngError::InvalidSetting(_))));
+
+        let hardware = TrueRandom::builder().source(EntropySource::HardwareRng).require_sources(true);
+        if EntropySource::HardwareRng.is_available() {
+            assert!(hardware.build().is_ok());
+        } else {
+            assert_eq!(invalid(hardware), Some(RngError::SourceUnavailable(EntropySource::HardwareRng)));
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! The KEM and key derivation with the `std` feature off.
+//!
+//! The crate itself builds as `no_std`; only this harness uses std. Without `std` there is
+//! no implicit randomness source, so everything here goes through the `*_with_rng` functions.
+//! Run with `cargo test --no-default-features --lib --tests`; the doc examples need `std`.
+
+use rand_core::{impls, Error, RngCore};
+use ruption_quantum_encrypt::{decapsulate, derive_keys, encapsulate_with_rng, keypair_with_rng};
+
+/// Xorshift generator standing in for an embedded target's hardware RNG.
+struct XorShift(u64);
+
+impl RngCore for XorShift {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[test]
+fn kem_round_trip_without_std() {
+    let (pk, sk) = keypair_with_rng(&mut XorShift(0x9e37_79b9_7f4a_7c15));
+    let (ct, ss) = encapsulate_with_rng(&pk, &mut XorShift(42));
+    assert!(decapsulate(&ct, &sk) == ss);
+    assert_eq!(derive_keys(&ss, 2).len(), 2);
+    let (same_pk, _) = keypair_with_rng(&mut XorShift(0x9e37_79b9_7f4a_7c15));
+    assert_eq!(same_pk, pk, "a caller-supplied RNG makes key generation reproducible");
+}
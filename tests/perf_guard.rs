@@ -0,0 +1,37 @@
+//! Coarse timing guard for CI: catches catastrophic slowdowns, not small regressions.
+//!
+//! Budgets are orders of magnitude above normal debug-build timings, so only an accidental
+//! complexity blow-up (e.g. an O(n³) loop in the lattice code) trips them.
+//! Run with `cargo test --test perf_guard -- --ignored`.
+
+#![cfg(feature = "std")]
+
+use ruption_quantum_encrypt::{decapsulate, derive_keys, encapsulate, keypair};
+use std::time::{Duration, Instant};
+
+const ROUNDS: u32 = 5;
+
+/// Runs `op` `ROUNDS` times and fails if the slowest run exceeds `budget`.
+fn assert_within(name: &str, budget: Duration, mut op: impl FnMut()) {
+    let mut slowest = Duration::ZERO;
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+        op();
+        slowest = slowest.max(start.elapsed());
+    }
+    assert!(slowest <= budget, "{name} took {slowest:?}, budget {budget:?}");
+}
+
+#[test]
+#[ignore = "timing-based; run explicitly in CI with --ignored"]
+fn core_operations_within_loose_budget() {
+    let budget = Duration::from_secs(2);
+    assert_within("keypair", budget, || drop(keypair()));
+
+    let (pk, sk) = keypair();
+    assert_within("encapsulate", budget, || drop(encapsulate(&pk)));
+
+    let (ct, ss) = encapsulate(&pk);
+    assert_within("decapsulate", budget, || drop(decapsulate(&ct, &sk)));
+    assert_within("derive_keys", budget, || drop(derive_keys(&ss, 1024)));
+}